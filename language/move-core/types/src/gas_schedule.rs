@@ -246,10 +246,11 @@ impl CostTable {
         &self.instruction_table[(instr_index - 1) as usize]
     }
 
+    /// `None` if the table has no entry for `native_index`, e.g. a stored gas schedule
+    /// that predates the native.
     #[inline]
-    pub fn native_cost(&self, native_index: u8) -> &GasCost {
-        precondition!(native_index < (self.native_table.len() as u8));
-        &self.native_table[native_index as usize]
+    pub fn native_cost(&self, native_index: u8) -> Option<&GasCost> {
+        self.native_table.get(native_index as usize)
     }
 }
 
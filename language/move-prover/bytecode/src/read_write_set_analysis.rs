@@ -660,7 +660,7 @@ fn call_native_function(
                 state.access_offset(args[0], Offset::VectorIndex, Access::Write, func_env);
             }
         }
-        ("Vector", "contains") => {
+        ("Vector", "contains") | ("Vector", "argmax_u128") | ("Vector", "argmin_u128") => {
             if state.locals.local_exists(args[0], func_env) {
                 state.record_access(args[0], Access::Read, func_env); // reads the length + contents
             }
@@ -682,13 +682,14 @@ fn call_native_function(
         ("Vector", "empty") | ("Vector", "destroy_empty") | ("Vector", "reverse") => (),
         ("Event", "write_to_event_store") => (),
         ("Hash", "sha3_256") | ("Hash", "sha2_256") => (),
+        ("BCS", "type_layout_hash") | ("BCS", "is_canonical") => (),
         ("Signature", "ed25519_validate_pubkey") | ("Signature", "ed25519_verify") => (),
         /////// 0L /////////
         ("Decimal", "demo") | ("Decimal", "single") | ("Decimal", "pair") => (),
         ("VDF", "verify") | ("VDF", "extract_address_from_challenge") => (),
         ("XHash", "keccak_256") => (),
         ("EthSignature", "recover") | ("EthSignature", "verify") => (),
-        ("Debug", "print") => (),
+        ("Debug", "print") | ("Debug", "call_depth") | ("Debug", "trace_span") => (),
         (m, f) => {
             panic!("Unsupported native function {:?}::{:?}", m, f)
         }
@@ -3,4 +3,10 @@ module Std::Debug {
     native public fun print<T>(x: &T);
 
     native public fun print_stack_trace();
+
+    /// Return the depth of the call stack, counting the calling function.
+    native public fun call_depth(): u64;
+
+    /// Emit a tracing event opening (`phase` 0) or closing (`phase` 1) the span `name`.
+    native public fun trace_span(name: vector<u8>, phase: u8);
 }
@@ -0,0 +1,19 @@
+#[test_only]
+module Std::DebugTests {
+    use Std::Debug;
+
+    fun depth_one_down(): u64 {
+        Debug::call_depth()
+    }
+
+    fun depth_two_down(): u64 {
+        depth_one_down()
+    }
+
+    #[test]
+    fun call_depth_increases_with_nesting() {
+        let here = Debug::call_depth();
+        assert!(depth_one_down() == here + 1, 0);
+        assert!(depth_two_down() == here + 2, 1);
+    }
+}
@@ -6,6 +6,16 @@ module Std::BCS {
     /// Return the binary representation of `v` in BCS (Binary Canonical Serialization) format
     native public fun to_bytes<MoveValue>(v: &MoveValue): vector<u8>;
 
+    /// Return a hash of the layout of `MoveValue`: the types and names of its fields, recursively.
+    /// Struct and module names are not included, so two structs with the same fields hash equal.
+    /// Comparing hashes detects layout changes across module upgrades.
+    native public fun type_layout_hash<MoveValue>(): vector<u8>;
+
+    /// Return true if `bytes` is the BCS encoding of some `MoveValue`, byte for byte: decoding it
+    /// and encoding the result gives back `bytes`. Malformed encodings, trailing bytes and
+    /// non-minimal lengths all return false.
+    native public fun is_canonical<MoveValue>(bytes: vector<u8>): bool;
+
     // ==============================
     // Module Specification
     spec module {} // switch to module documentation context
@@ -9,4 +9,24 @@ module Std::UnitTest {
     /// This will cause a linking failure if an attempt is made to publish a
     /// test module in a VM that isn't in unit test mode.
     native public fun create_signers_for_testing(num_signers: u64): vector<signer>;
+
+    /// Return one signer for each address in `addresses`, in the same order.
+    /// Lets tests pin the accounts their fixtures run under.
+    native public fun create_signers_for_testing_at(addresses: vector<address>): vector<signer>;
+
+    /// Abort with code 1 if `cond` is false. The UTF-8 `msg` is shown by the
+    /// test runner next to the failure.
+    native public fun assert_with_msg(cond: bool, msg: vector<u8>);
+
+    /// Return the gas consumed so far by the current test. The value never
+    /// decreases within an execution, so the difference between two readings is
+    /// the cost of the code run between them.
+    native public fun gas_used(): u64;
+
+    /// Return the current time of the deterministic test clock, in microseconds.
+    /// The clock starts at zero and only moves when set with `set_now_micros`.
+    native public fun now_micros(): u64;
+
+    /// Set the deterministic test clock to `micros`.
+    native public fun set_now_micros(micros: u64);
 }
@@ -42,6 +42,14 @@ module Std::Vector {
     /// Aborts if `i`or `j` is out of bounds.
     native public fun swap<Element>(v: &mut vector<Element>, i: u64, j: u64);
 
+    /// Return the largest element of `v` and the index of its first occurrence.
+    /// Aborts if `v` is empty.
+    native public fun argmax_u128(v: &vector<u128>): (u128, u64);
+
+    /// Return the smallest element of `v` and the index of its first occurrence.
+    /// Aborts if `v` is empty.
+    native public fun argmin_u128(v: &vector<u128>): (u128, u64);
+
     /// Return an vector of size one containing element `e`.
     public fun singleton<Element>(e: Element): vector<Element> {
         let v = empty();
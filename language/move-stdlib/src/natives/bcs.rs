@@ -11,6 +11,7 @@ use move_vm_types::{
     pop_arg,
     values::{values_impl::Reference, Value},
 };
+use sha3::{Digest, Sha3_256};
 use smallvec::smallvec;
 use std::collections::VecDeque;
 
@@ -33,7 +34,7 @@ pub fn native_to_bytes(
     };
     let serialized_value = match serialized_value_opt {
         None => {
-            let cost = native_gas(context.cost_table(), NativeCostIndex::BCS_TO_BYTES, 1);
+            let cost = native_gas(context.cost_table(), NativeCostIndex::BCS_TO_BYTES, 1)?;
             return Ok(NativeResult::err(cost, NFE_BCS_SERIALIZATION_FAILURE));
         }
         Some(serialized_value) => serialized_value,
@@ -44,10 +45,80 @@ pub fn native_to_bytes(
         context.cost_table(),
         NativeCostIndex::BCS_TO_BYTES,
         serialized_value.len(),
-    );
+    )?;
 
     Ok(NativeResult::ok(
         cost,
         smallvec![Value::vector_u8(serialized_value)],
     ))
 }
+
+/// Rust implementation of Move's `native public fun type_layout_hash<T>(): vector<u8>`
+/// The SHA3-256 hash of the BCS encoding of `T`'s layout, with struct field names. Struct and
+/// module names are not part of the layout, so structs with the same fields hash equal.
+pub fn native_type_layout_hash(
+    context: &mut NativeContext,
+    mut ty_args: Vec<Type>,
+    args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 1);
+    debug_assert!(args.is_empty());
+
+    let ty = ty_args.pop().unwrap();
+    let encoded_layout = match context.type_to_fully_annotated_layout(&ty)? {
+        Some(layout) => ::bcs::to_bytes(&layout).ok(),
+        None => None,
+    };
+    let encoded_layout = match encoded_layout {
+        None => {
+            let cost = native_gas(context.cost_table(), NativeCostIndex::BCS_TO_BYTES, 1)?;
+            return Ok(NativeResult::err(cost, NFE_BCS_SERIALIZATION_FAILURE));
+        }
+        Some(encoded_layout) => encoded_layout,
+    };
+
+    // cost is proportional to the size of the encoded layout
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::BCS_TO_BYTES,
+        encoded_layout.len(),
+    )?;
+
+    let hash = Sha3_256::digest(&encoded_layout).to_vec();
+    Ok(NativeResult::ok(cost, smallvec![Value::vector_u8(hash)]))
+}
+
+/// Rust implementation of Move's `native public fun is_canonical<T>(bytes: vector<u8>): bool`
+/// Whether `bytes` deserializes as a `T` that serializes back to exactly `bytes`. Bytes which
+/// do not deserialize at all are not canonical.
+pub fn native_is_canonical(
+    context: &mut NativeContext,
+    mut ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 1);
+    debug_assert!(args.len() == 1);
+
+    let bytes = pop_arg!(args, Vec<u8>);
+
+    let ty = ty_args.pop().unwrap();
+    let layout = match context.type_to_type_layout(&ty)? {
+        None => {
+            let cost = native_gas(context.cost_table(), NativeCostIndex::BCS_TO_BYTES, 1)?;
+            return Ok(NativeResult::err(cost, NFE_BCS_SERIALIZATION_FAILURE));
+        }
+        Some(layout) => layout,
+    };
+
+    // cost is proportional to the size of the input, which is read and written once each
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::BCS_TO_BYTES,
+        2 * bytes.len(),
+    )?;
+
+    let reserialized = Value::simple_deserialize(&bytes, &layout)
+        .and_then(|value| value.simple_serialize(&layout));
+    let canonical = reserialized.as_ref() == Some(&bytes);
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(canonical)]))
+}
@@ -13,6 +13,11 @@ use move_vm_types::{
 use smallvec::smallvec;
 use std::collections::VecDeque;
 
+/// `phase` value opening a span in `trace_span`.
+const TRACE_SPAN_BEGIN: u8 = 0;
+/// `phase` value closing a span in `trace_span`.
+const TRACE_SPAN_END: u8 = 1;
+
 #[allow(unused_mut)]
 #[allow(unused_variables)]
 pub fn native_print(
@@ -55,3 +60,97 @@ pub fn native_print_stack_trace(
 
     Ok(NativeResult::ok(ONE_GAS_UNIT, smallvec![]))
 }
+
+pub fn native_call_depth(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.is_empty());
+
+    let depth = context.call_depth() as u64;
+
+    Ok(NativeResult::ok(ONE_GAS_UNIT, smallvec![Value::u64(depth)]))
+}
+
+/// Emits a `tracing` event marking the beginning or the end of a named span.
+/// When no subscriber is interested the event is skipped, but gas is still charged.
+pub fn native_trace_span(
+    _context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 2);
+
+    let phase = pop_arg!(args, u8);
+    let name = pop_arg!(args, Vec<u8>);
+
+    trace_span(&name, phase);
+
+    Ok(NativeResult::ok(ONE_GAS_UNIT, smallvec![]))
+}
+
+fn trace_span(name: &[u8], phase: u8) {
+    match phase {
+        TRACE_SPAN_BEGIN => tracing::trace!(
+            target: "move_vm::natives",
+            span = %String::from_utf8_lossy(name),
+            phase = "begin"
+        ),
+        TRACE_SPAN_END => tracing::trace!(
+            target: "move_vm::natives",
+            span = %String::from_utf8_lossy(name),
+            phase = "end"
+        ),
+        _ => {}
+    }
+}
+
+#[test]
+fn test_trace_span_is_recorded() {
+    use std::sync::{Arc, Mutex};
+    use tracing::{
+        field::{Field, Visit},
+        span, Event, Metadata, Subscriber,
+    };
+
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+    struct Fields(String);
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0.push_str(&format!("{}={:?} ", field.name(), value));
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+            span::Id::from_u64(1)
+        }
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+        fn event(&self, event: &Event<'_>) {
+            let mut fields = Fields(String::new());
+            event.record(&mut fields);
+            self.0.lock().unwrap().push(fields.0);
+        }
+        fn enter(&self, _: &span::Id) {}
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    let events = Arc::new(Mutex::new(vec![]));
+    tracing::subscriber::with_default(Recorder(events.clone()), || {
+        trace_span(b"transfer", TRACE_SPAN_BEGIN);
+        trace_span(b"transfer", TRACE_SPAN_END);
+    });
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 2);
+    assert!(events[0].contains("span=transfer") && events[0].contains("phase=\"begin\""));
+    assert!(events[1].contains("span=transfer") && events[1].contains("phase=\"end\""));
+}
@@ -29,7 +29,7 @@ pub fn native_sha2_256(
         context.cost_table(),
         NativeCostIndex::SHA2_256,
         hash_arg.len(),
-    );
+    )?;
 
     let hash_vec = Sha256::digest(hash_arg.as_slice()).to_vec();
     Ok(NativeResult::ok(
@@ -52,7 +52,7 @@ pub fn native_sha3_256(
         context.cost_table(),
         NativeCostIndex::SHA3_256,
         hash_arg.len(),
-    );
+    )?;
 
     let hash_vec = Sha3_256::digest(hash_arg.as_slice()).to_vec();
     Ok(NativeResult::ok(
@@ -12,6 +12,13 @@ pub mod ol_counters;
 pub mod ol_decimal;
 pub mod ol_hash;
 pub mod ol_eth_signature;
+pub mod ol_math;
+pub mod ol_bits;
+pub mod ol_ed25519;
+pub mod ol_evm;
+pub mod ol_merkle;
+pub mod ol_address;
+pub mod ol_p256;
 
 #[cfg(feature = "testing")]
 pub mod unit_test;
@@ -25,8 +32,18 @@ use move_core_types::{account_address::AccountAddress, identifier::Identifier};
 use move_vm_runtime::native_functions::{NativeFunction, NativeFunctionTable};
 
 pub fn all_natives(move_std_addr: AccountAddress) -> NativeFunctionTable {
+    all_natives_with_vdf_parameters::<ol_vdf::DefaultVdfGasParameters>(move_std_addr)
+}
+
+/// Like `all_natives`, but the VDF natives use the parameters of `P`, e.g. to accept larger
+/// security parameters on a testnet. Every validator of a network must register the same `P`.
+pub fn all_natives_with_vdf_parameters<P: ol_vdf::VdfGasParameters>(
+    move_std_addr: AccountAddress,
+) -> NativeFunctionTable {
     const NATIVES: &[(&str, &str, NativeFunction)] = &[
         ("BCS", "to_bytes", bcs::native_to_bytes),
+        ("BCS", "type_layout_hash", bcs::native_type_layout_hash),
+        ("BCS", "is_canonical", bcs::native_is_canonical),
         ("Event", "write_to_event_store", event::write_to_event_store),
         ("Hash", "sha2_256", hash::native_sha2_256),
         ("Hash", "sha3_256", hash::native_sha3_256),
@@ -39,6 +56,8 @@ pub fn all_natives(move_std_addr: AccountAddress) -> NativeFunctionTable {
         ("Vector", "pop_back", vector::native_pop),
         ("Vector", "destroy_empty", vector::native_destroy_empty),
         ("Vector", "swap", vector::native_swap),
+        ("Vector", "argmax_u128", vector::native_argmax_u128),
+        ("Vector", "argmin_u128", vector::native_argmin_u128),
         //////// 0L ////////
         // 0L needs these to be compiled normally to use in `swarm` and integration tests.
         // #[cfg(feature = "testing")]
@@ -51,24 +70,97 @@ pub fn all_natives(move_std_addr: AccountAddress) -> NativeFunctionTable {
             "print_stack_trace",
             debug::native_print_stack_trace,
         ),
+        ("Debug", "call_depth", debug::native_call_depth),
+        ("Debug", "trace_span", debug::native_trace_span),
         #[cfg(feature = "testing")]
         (
             "UnitTest",
             "create_signers_for_testing",
             unit_test::native_create_signers_for_testing,
         ),
+        #[cfg(feature = "testing")]
+        (
+            "UnitTest",
+            "create_signers_for_testing_at",
+            unit_test::native_create_signers_for_testing_at,
+        ),
+        #[cfg(feature = "testing")]
+        ("UnitTest", "assert_with_msg", unit_test::native_assert_with_msg),
+        #[cfg(feature = "testing")]
+        ("UnitTest", "gas_used", unit_test::native_gas_used),
+        #[cfg(feature = "testing")]
+        ("UnitTest", "now_micros", unit_test::native_test_now_micros),
+        #[cfg(feature = "testing")]
+        ("UnitTest", "set_now_micros", unit_test::native_set_test_now_micros),
         /////// 0L /////////
-        ("VDF", "verify", ol_vdf::native_verify),
         ("VDF", "extract_address_from_challenge", ol_vdf::native_extract_address_from_challenge),
         ("Decimal", "demo", ol_decimal::native_demo),
         ("Decimal", "single", ol_decimal::native_single),
         ("Decimal", "pair", ol_decimal::native_pair),
+        ("Decimal", "cmp", ol_decimal::native_cmp),
+        ("Decimal", "compare", ol_decimal::native_compare),
+        ("Decimal", "complement_to", ol_decimal::native_complement_to),
+        ("Decimal", "abs_diff", ol_decimal::native_abs_diff),
+        ("Decimal", "convert", ol_decimal::native_convert),
+        ("Decimal", "from_ratio", ol_decimal::native_from_ratio),
+        ("Decimal", "same_sign", ol_decimal::native_same_sign),
+        ("Decimal", "scale_at_most", ol_decimal::native_scale_at_most),
+        ("Decimal", "twap_update", ol_decimal::native_twap_update),
+        ("Decimal", "present_value", ol_decimal::native_present_value),
+        ("Decimal", "to_string", ol_decimal::native_to_string),
+        ("Decimal", "from_string", ol_decimal::native_from_string),
+        ("Decimal", "check_sum_equals", ol_decimal::native_check_sum_equals),
         ("XHash", "keccak_256", ol_hash::native_keccak_256),
+        ("XHash", "keccak_512", ol_hash::native_keccak_512),
+        ("XHash", "keccak_256_chunks", ol_hash::native_keccak_256_chunks),
+        ("XHash", "keccak_512_chunks", ol_hash::native_keccak_512_chunks),
+        ("XHash", "sha3_512", ol_hash::native_sha3_512),
+        ("XHash", "blake3", ol_hash::native_blake3),
+        ("XHash", "u128_order_preserving", ol_hash::native_u128_order_preserving),
+        ("XHash", "i128_order_preserving", ol_hash::native_i128_order_preserving),
+        ("XHash", "unique_id", ol_hash::native_unique_id),
+        ("XHash", "verify_merkle_batch", ol_hash::native_verify_merkle_batch),
+        ("XHash", "nested_mapping_slot", ol_hash::native_nested_mapping_slot),
+        ("XHash", "array_element_slot", ol_hash::native_array_element_slot),
+        ("XHash", "airdrop_leaf", ol_hash::native_airdrop_leaf),
+        ("XHash", "chunk_id", ol_hash::native_chunk_id),
+        ("XHash", "multiset_hash", ol_hash::native_multiset_hash),
+        ("XHash", "verify_hashchain", ol_hash::native_verify_hashchain),
+        ("XHash", "keccak_packed", ol_hash::native_keccak_packed),
         ("EthSignature", "recover", ol_eth_signature::native_recover),
+        ("EthSignature", "recover_pubkey", ol_eth_signature::native_recover_pubkey),
         ("EthSignature", "verify", ol_eth_signature::native_verify),
+        ("EthSignature", "verify_batch", ol_eth_signature::native_verify_batch),
+        ("EthSignature", "verify_strict", ol_eth_signature::native_verify_strict),
+        ("EthSignature", "recover_typed_data", ol_eth_signature::native_recover_typed_data),
+        ("Ed25519", "ed25519ph_verify", ol_ed25519::native_ed25519ph_verify),
+        ("P256", "webauthn_verify", ol_p256::native_webauthn_verify),
+        ("XEvm", "semver_compare", ol_evm::native_semver_compare),
+        ("XEvm", "log_matches_filter", ol_evm::native_log_matches_filter),
+        ("XEvm", "abi_encode_hash", ol_evm::native_abi_encode_hash),
+        ("XEvm", "ens_namehash", ol_evm::native_ens_namehash),
+        ("XMerkle", "smt_leaf_hash", ol_merkle::native_smt_leaf_hash),
+        ("XMerkle", "smt_internal_hash", ol_merkle::native_smt_internal_hash),
+        ("XMerkle", "verify_non_inclusion", ol_merkle::native_smt_verify_non_inclusion),
+        ("XAddress", "derive_nonce", ol_address::native_derive_nonce),
+        ("XMath", "lerp", ol_math::native_lerp_u128),
+        ("XMath", "bit_length", ol_math::native_bit_length),
+        ("XMath", "saturating_add", ol_math::native_saturating_add),
+        ("XMath", "saturating_sub", ol_math::native_saturating_sub),
+        ("XMath", "align_up", ol_math::native_align_up),
+        ("XMath", "checked_sum", ol_math::native_checked_sum),
+        ("XBits", "is_power_of_two", ol_bits::native_is_power_of_two),
+        ("XBits", "next_power_of_two", ol_bits::native_next_power_of_two),
+    ];
+    let vdf_natives: &[(&str, &str, NativeFunction)] = &[
+        ("VDF", "verify", ol_vdf::native_verify::<P>),
+        ("VDF", "verify_with_scheme", ol_vdf::native_verify_with_scheme::<P>),
+        #[cfg(feature = "testing")]
+        ("VDF", "prove", ol_vdf::native_prove::<P>),
     ];
     NATIVES
         .iter()
+        .chain(vdf_natives)
         .cloned()
         .map(|(module_name, func_name, func)| {
             (
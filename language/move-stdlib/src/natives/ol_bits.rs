@@ -0,0 +1,95 @@
+//////// 0L ////////
+// SPDX-License-Identifier: Apache-2.0
+use move_binary_format::errors::PartialVMResult;
+use move_vm_runtime::native_functions::NativeContext;
+use move_vm_types::{
+    gas_schedule::NativeCostIndex,
+    loaded_data::runtime_types::Type,
+    natives::function::{native_gas, NativeResult},
+    pop_arg,
+    values::Value,
+};
+use smallvec::smallvec;
+use std::collections::VecDeque;
+
+/// Rust implementation of Move's `native public fun is_power_of_two(x: u128): bool`
+/// Zero is not a power of two.
+pub fn native_is_power_of_two(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let x = pop_arg!(arguments, u128);
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::DECIMAL, 1)?;
+
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(is_power_of_two(x))]))
+}
+
+fn is_power_of_two(x: u128) -> bool {
+    x.count_ones() == 1
+}
+
+/// Rust implementation of Move's `native public fun next_power_of_two(x: u128): (bool, u128)`
+/// Returns the smallest power of two not below `x`, so zero rounds up to one.
+/// The flag is false, with a zero result, when that power does not fit in a u128.
+pub fn native_next_power_of_two(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let x = pop_arg!(arguments, u128);
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::DECIMAL, 1)?;
+
+    let (fits, power) = match next_power_of_two(x) {
+        Some(power) => (true, power),
+        None => (false, 0),
+    };
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::bool(fits), Value::u128(power)],
+    ))
+}
+
+fn next_power_of_two(x: u128) -> Option<u128> {
+    if x <= 1 {
+        return Some(1);
+    }
+    // the highest bit of `x - 1`, moved up by one, is the first power of two not below `x`
+    match (x - 1).leading_zeros() {
+        0 => None,
+        zeros => Some(1 << (128 - zeros)),
+    }
+}
+
+#[test]
+fn test_is_power_of_two() {
+    assert!(!is_power_of_two(0));
+    assert!(is_power_of_two(1));
+    assert!(is_power_of_two(2));
+    assert!(!is_power_of_two(3));
+    assert!(!is_power_of_two(6));
+    assert!(is_power_of_two(1 << 127));
+    assert!(!is_power_of_two(u128::MAX));
+}
+
+#[test]
+fn test_next_power_of_two() {
+    assert_eq!(next_power_of_two(0), Some(1));
+    assert_eq!(next_power_of_two(1), Some(1));
+    assert_eq!(next_power_of_two(2), Some(2));
+    assert_eq!(next_power_of_two(5), Some(8));
+    assert_eq!(next_power_of_two(8), Some(8));
+    assert_eq!(next_power_of_two(1 << 127), Some(1 << 127));
+    // the first value whose next power of two overflows
+    assert_eq!(next_power_of_two((1 << 127) + 1), None);
+    assert_eq!(next_power_of_two(u128::MAX), None);
+}
@@ -1,7 +1,10 @@
 //////// 0L ////////
 // SPDX-License-Identifier: Apache-2.0
 use move_binary_format::errors::{PartialVMError, PartialVMResult};
-use move_core_types::vm_status::StatusCode;
+use move_core_types::{
+    gas_schedule::{CostTable, GasCarrier, InternalGasUnits},
+    vm_status::StatusCode,
+};
 use move_vm_runtime::native_functions::NativeContext;
 use move_vm_types::{
     gas_schedule::NativeCostIndex,
@@ -10,9 +13,29 @@ use move_vm_types::{
     pop_arg,
     values::Value,
 };
-use rust_decimal::{self, Decimal, MathematicalOps, RoundingStrategy, prelude::ToPrimitive};
+use rust_decimal::{
+    self, Decimal, MathematicalOps, RoundingStrategy,
+    prelude::{FromPrimitive, ToPrimitive},
+};
 use smallvec::smallvec;
-use std::collections::VecDeque;
+use std::{cmp::Ordering, collections::VecDeque, convert::TryFrom, str::FromStr};
+
+// Abort codes returned to Move when a decimal operation cannot produce a result.
+// The natives never panic on user supplied operands.
+
+/// The operand is outside of the domain of the operation, e.g. the square root
+/// or logarithm of a negative number, or a negative rescale target.
+const EDECIMAL_INVALID_DOMAIN: u64 = 1;
+/// An operand or the result does not fit in a `Decimal`.
+const EDECIMAL_OVERFLOW: u64 = 2;
+/// The right operand of a division or remainder is zero.
+const EDECIMAL_DIVIDE_BY_ZERO: u64 = 3;
+/// Parallel vectors describing a list of decimals have different lengths.
+const EDECIMAL_LENGTH_MISMATCH: u64 = 4;
+/// The bytes are not a decimal number.
+const EDECIMAL_MALFORMED_STRING: u64 = 5;
+/// A TWAP accumulator is neither empty nor a 16 byte encoded `Decimal`.
+const EDECIMAL_MALFORMED_ACCUMULATOR: u64 = 6;
 
 #[derive(Debug)]
 struct MoveDecimalType {
@@ -25,22 +48,23 @@ impl MoveDecimalType {
     fn new(scale: u8, int: u128, sign: bool) -> Self {
         MoveDecimalType { sign, int, scale }
     }
-    fn into_decimal(&self) -> Decimal {
-        let scale_right = self.scale as u32;
-        let mut signed_int_right = self.int as i128;
-        if !self.sign {
-            signed_int_right = signed_int_right * -1
-        }
-
-        Decimal::from_i128_with_scale(signed_int_right, scale_right)
+    /// `Err(EDECIMAL_OVERFLOW)` if the mantissa needs more than 96 bits or the scale is
+    /// above 28, which a `Decimal` cannot hold.
+    fn into_decimal(&self) -> Result<Decimal, u64> {
+        let int = i128::try_from(self.int).map_err(|_| EDECIMAL_OVERFLOW)?;
+        let signed_int = if self.sign { int } else { -int };
+        Decimal::try_from_i128_with_scale(signed_int, self.scale as u32)
+            .map_err(|_| EDECIMAL_OVERFLOW)
     }
 
-    fn from_decimal(dec: Decimal) -> MoveDecimalType {
-        MoveDecimalType {
-            sign: dec.is_sign_positive(),
-            int: dec.mantissa().abs() as u128,
-            scale: dec.scale() as u8,
-        }
+    /// `None` if `dec` cannot be represented as a Move decimal, instead of truncating it.
+    fn from_decimal(dec: Decimal) -> Option<MoveDecimalType> {
+        Some(MoveDecimalType {
+            // `Decimal` can carry a negative zero, Move always sees zero as positive.
+            sign: dec.is_zero() || dec.is_sign_positive(),
+            int: u128::try_from(dec.mantissa().checked_abs()?).ok()?,
+            scale: u8::try_from(dec.scale()).ok()?,
+        })
     }
 }
 
@@ -58,15 +82,13 @@ pub fn native_demo(
     let sign = pop_arg!(arguments, bool);
 
     let m = MoveDecimalType::new(scale, int, sign);
-    let dec = m.into_decimal();
 
-    let cost = native_gas(
-        context.cost_table(),
-        NativeCostIndex::DECIMAL,
-        m.int.to_be_bytes().len(),
-    );
+    let cost = decimal_cost(context.cost_table(), &m, 1)?;
 
-    let new_m = MoveDecimalType::from_decimal(dec);
+    let new_m = match m.into_decimal().and_then(to_move_decimal) {
+        Ok(new_m) => new_m,
+        Err(abort_code) => return Ok(NativeResult::err(cost, abort_code)),
+    };
 
     Ok(NativeResult::ok(
         cost,
@@ -94,21 +116,20 @@ pub fn native_single(
 
 
     let m = MoveDecimalType::new(scale, int, sign);
-    let dec = m.into_decimal();
 
-    let result = match op_id {
-        100 => dec.sqrt().unwrap().normalize(),
-        101 => dec.trunc(),
-        _ => return Err(PartialVMError::new(StatusCode::INDEX_OUT_OF_BOUNDS)),
+    let cost = decimal_cost(context.cost_table(), &m, 1)?;
+
+    let dec = match m.into_decimal() {
+        Ok(dec) => dec,
+        Err(abort_code) => return Ok(NativeResult::err(cost, abort_code)),
     };
 
-    let cost = native_gas(
-        context.cost_table(),
-        NativeCostIndex::DECIMAL,
-        m.int.to_be_bytes().len(),
-    );
+    let result = single_op(op_id, dec)?;
 
-    let out = MoveDecimalType::from_decimal(result);
+    let out = match result.and_then(to_move_decimal) {
+        Ok(out) => out,
+        Err(abort_code) => return Ok(NativeResult::err(cost, abort_code)),
+    };
 
     Ok(NativeResult::ok(
         cost,
@@ -135,7 +156,6 @@ pub fn native_pair(
 
 
     let m_right = MoveDecimalType::new(scale_right, int_right, sign_right);
-    let dec_right = m_right.into_decimal();
 
     // pop arguments in reverse order
     let scale_left = pop_arg!(arguments, u8);
@@ -143,7 +163,6 @@ pub fn native_pair(
     let sign_left = pop_arg!(arguments, bool);
 
     let m_left = MoveDecimalType::new(scale_left, int_left, sign_left);
-    let mut dec_left = m_left.into_decimal();
 
     let strategy = match pop_arg!(arguments, u8) {
       0=> RoundingStrategy::MidpointNearestEven,
@@ -154,42 +173,216 @@ pub fn native_pair(
 
     let op_id = pop_arg!(arguments, u8);
 
-    dbg!(&op_id);
-    dbg!(&dec_left);
-    dbg!(&dec_right);
-    
-    let result = match op_id {
-        0 => {
-            dec_left.rescale(dec_right.trunc().to_u32().unwrap());
-            dec_left
+    let op_weight = match op_id {
+        5 => pow_weight(&m_right),
+        _ => pair_op_weight(op_id),
+    };
+    let cost = decimal_cost(context.cost_table(), &m_left, op_weight)?;
+
+    let (dec_left, dec_right) = match into_decimal_pair(&m_left, &m_right) {
+        Ok(pair) => pair,
+        Err(abort_code) => return Ok(NativeResult::err(cost, abort_code)),
+    };
+
+    let result = pair_op(op_id, dec_left, dec_right, strategy)?;
+    tracing::debug!(
+        target: "move_vm::natives",
+        op_id,
+        left = %dec_left,
+        right = %dec_right,
+        result = ?result,
+        "decimal pair"
+    );
+
+    let out = match result.and_then(to_move_decimal) {
+        Ok(out) => out,
+        Err(abort_code) => return Ok(NativeResult::err(cost, abort_code)),
+    };
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![
+            Value::bool(out.sign),
+            Value::u128(out.int),
+            Value::u8(out.scale)
+        ],
+    ))
+}
+
+/// Rust implementation of Move's `native public fun complement_to(sign: bool, int: u128,
+/// scale: u8, whole_sign: bool, whole_int: u128, whole_scale: u8): (bool, u128, u8)`
+/// Returns `whole - value`, aborting with `EDECIMAL_OVERFLOW` if it does not fit.
+pub fn native_complement_to(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 6);
+
+    // pop arguments in reverse order
+    let whole_scale = pop_arg!(arguments, u8);
+    let whole_int = pop_arg!(arguments, u128);
+    let whole_sign = pop_arg!(arguments, bool);
+    let m_whole = MoveDecimalType::new(whole_scale, whole_int, whole_sign);
+
+    let scale = pop_arg!(arguments, u8);
+    let int = pop_arg!(arguments, u128);
+    let sign = pop_arg!(arguments, bool);
+    let m = MoveDecimalType::new(scale, int, sign);
+
+    let cost = decimal_cost(context.cost_table(), &m, 1)?;
+
+    let result = into_decimal_pair(&m, &m_whole)
+        .and_then(|(value, whole)| complement_to(value, whole));
+    let out = match result.and_then(to_move_decimal) {
+        Ok(out) => out,
+        Err(abort_code) => return Ok(NativeResult::err(cost, abort_code)),
+    };
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![
+            Value::bool(out.sign),
+            Value::u128(out.int),
+            Value::u8(out.scale)
+        ],
+    ))
+}
+
+fn complement_to(value: Decimal, whole: Decimal) -> Result<Decimal, u64> {
+    checked(whole.checked_sub(value))
+}
+
+/// Rust implementation of Move's `native public fun abs_diff(sign_a: bool, int_a: u128,
+/// scale_a: u8, sign_b: bool, int_b: u128, scale_b: u8): (bool, u128, u8)`
+/// Returns `|a - b|`, aborting with `EDECIMAL_OVERFLOW` if it does not fit.
+pub fn native_abs_diff(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 6);
+
+    // pop arguments in reverse order
+    let scale_b = pop_arg!(arguments, u8);
+    let int_b = pop_arg!(arguments, u128);
+    let sign_b = pop_arg!(arguments, bool);
+    let m_b = MoveDecimalType::new(scale_b, int_b, sign_b);
+
+    let scale_a = pop_arg!(arguments, u8);
+    let int_a = pop_arg!(arguments, u128);
+    let sign_a = pop_arg!(arguments, bool);
+    let m_a = MoveDecimalType::new(scale_a, int_a, sign_a);
+
+    let cost = decimal_cost(context.cost_table(), &m_a, 1)?;
+
+    let result = into_decimal_pair(&m_a, &m_b).and_then(|(a, b)| abs_diff(a, b));
+    let out = match result.and_then(to_move_decimal) {
+        Ok(out) => out,
+        Err(abort_code) => return Ok(NativeResult::err(cost, abort_code)),
+    };
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![
+            Value::bool(out.sign),
+            Value::u128(out.int),
+            Value::u8(out.scale)
+        ],
+    ))
+}
+
+fn abs_diff(a: Decimal, b: Decimal) -> Result<Decimal, u64> {
+    checked(a.checked_sub(b)).map(|diff| diff.abs())
+}
+
+/// Rust implementation of Move's `native public fun convert(amount: u128, rate_sign: bool,
+/// rate_int: u128, rate_scale: u8, invert: bool): u128`
+/// Returns `amount * rate`, or `amount / rate` when `invert` is set, rounded down.
+/// A negative rate aborts with `EDECIMAL_INVALID_DOMAIN`.
+pub fn native_convert(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 5);
+
+    // pop arguments in reverse order
+    let invert = pop_arg!(arguments, bool);
+    let rate_scale = pop_arg!(arguments, u8);
+    let rate_int = pop_arg!(arguments, u128);
+    let rate_sign = pop_arg!(arguments, bool);
+    let m_rate = MoveDecimalType::new(rate_scale, rate_int, rate_sign);
+    let amount = pop_arg!(arguments, u128);
+
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::DECIMAL,
+        significant_bytes(amount),
+    )?;
+
+    match m_rate.into_decimal().and_then(|rate| convert(amount, rate, invert)) {
+        Ok(converted) => Ok(NativeResult::ok(cost, smallvec![Value::u128(converted)])),
+        Err(abort_code) => Ok(NativeResult::err(cost, abort_code)),
+    }
+}
+
+fn convert(amount: u128, rate: Decimal, invert: bool) -> Result<u128, u64> {
+    if rate.is_sign_negative() && !rate.is_zero() {
+        return Err(EDECIMAL_INVALID_DOMAIN);
+    }
+    let amount = Decimal::from_u128(amount).ok_or(EDECIMAL_OVERFLOW)?;
+    let converted = if invert {
+        if rate.is_zero() {
+            return Err(EDECIMAL_DIVIDE_BY_ZERO);
         }
-        1 => dec_left.checked_add(dec_right).unwrap().normalize(),
-        2 => dec_left.checked_sub(dec_right).unwrap().normalize(),
-        3 => dec_left.checked_mul(dec_right).unwrap().normalize(),
-        4 => dec_left.checked_div(dec_right).unwrap().normalize(),
-        5 => {
-            let pow = dec_right.to_f64().unwrap();
-            dec_left.powf(pow).normalize()
-        },
-        6 => {
-            // let pow = dec_right.to_f64().unwrap();
-            dec_left.round_dp_with_strategy(dec_right.trunc().to_u32().unwrap(), strategy)
-        },
-        _ => return Err(PartialVMError::new(StatusCode::INDEX_OUT_OF_BOUNDS)),
+        checked(amount.checked_div(rate))?
+    } else {
+        checked(amount.checked_mul(rate))?
     };
+    converted.floor().to_u128().ok_or(EDECIMAL_OVERFLOW)
+}
 
-    let out = MoveDecimalType::from_decimal(result);
-    dbg!(&out);
+/// Rust implementation of Move's `native public fun from_ratio(numerator: u128,
+/// denominator: u128, scale: u8): (bool, bool, u128, u8)`
+/// Returns `numerator / denominator` rounded to `scale` decimal places, after a success flag
+/// which is false, with a zero result, when the denominator is zero.
+/// Aborts with `EDECIMAL_OVERFLOW` if the numerator does not fit in a `Decimal`.
+pub fn native_from_ratio(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
+
+    // pop arguments in reverse order
+    let scale = pop_arg!(arguments, u8);
+    let denominator = pop_arg!(arguments, u128);
+    let numerator = pop_arg!(arguments, u128);
 
     let cost = native_gas(
         context.cost_table(),
         NativeCostIndex::DECIMAL,
-        m_left.int.to_be_bytes().len(),
-    );
+        significant_bytes(numerator) * pair_op_weight(4),
+    )?;
+
+    let (ok, out) = match from_ratio(numerator, denominator, scale) {
+        Ok(Some(dec)) => match to_move_decimal(dec) {
+            Ok(out) => (true, out),
+            Err(abort_code) => return Ok(NativeResult::err(cost, abort_code)),
+        },
+        Ok(None) => (false, MoveDecimalType::new(0, 0, true)),
+        Err(abort_code) => return Ok(NativeResult::err(cost, abort_code)),
+    };
 
     Ok(NativeResult::ok(
         cost,
         smallvec![
+            Value::bool(ok),
             Value::bool(out.sign),
             Value::u128(out.int),
             Value::u8(out.scale)
@@ -197,75 +390,1197 @@ pub fn native_pair(
     ))
 }
 
-#[test]
-fn test_into_dec() {
-    let m = MoveDecimalType {
-        sign: true,
-        int: Decimal::MAX.to_u128().unwrap(), //79228162514264337593543950335
-        scale: 0,
+// `Ok(None)` is a zero denominator. The result carries exactly `scale` decimal places,
+// up to the 28 a `Decimal` can hold.
+fn from_ratio(numerator: u128, denominator: u128, scale: u8) -> Result<Option<Decimal>, u64> {
+    if denominator == 0 {
+        return Ok(None);
+    }
+    let numerator = Decimal::from_u128(numerator).ok_or(EDECIMAL_OVERFLOW)?;
+    // a denominator too large for a `Decimal` makes the ratio round to zero
+    let mut ratio = match Decimal::from_u128(denominator) {
+        Some(denominator) => checked(numerator.checked_div(denominator))?,
+        None => Decimal::ZERO,
     };
+    ratio = ratio.round_dp(scale as u32);
+    ratio.rescale(scale as u32);
+    Ok(Some(ratio))
+}
 
-    let dec = m.into_decimal();
+/// Rust implementation of Move's `native public fun same_sign(sign_a: bool, int_a: u128,
+/// scale_a: u8, sign_b: bool, int_b: u128, scale_b: u8): bool`
+/// Zero counts as positive, whatever its sign flag.
+pub fn native_same_sign(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 6);
 
-    // dbg!(&dec.to_string());
-    assert_eq!(dec.to_u128(), Some(m.int));
+    // pop arguments in reverse order
+    let scale_b = pop_arg!(arguments, u8);
+    let int_b = pop_arg!(arguments, u128);
+    let sign_b = pop_arg!(arguments, bool);
+    let m_b = MoveDecimalType::new(scale_b, int_b, sign_b);
 
-    let new_m = MoveDecimalType::from_decimal(dec);
-    // dbg!(&new_m);
-    assert_eq!(m.int, new_m.int);
+    let scale_a = pop_arg!(arguments, u8);
+    let int_a = pop_arg!(arguments, u128);
+    let sign_a = pop_arg!(arguments, bool);
+    let m_a = MoveDecimalType::new(scale_a, int_a, sign_a);
 
+    let cost = decimal_cost(context.cost_table(), &m_a, 1)?;
 
-    let new_dec = new_m.into_decimal();
-    // dbg!(&new_dec.to_string());
-    assert_eq!(new_dec.to_u128(), Some(m.int));
+    match into_decimal_pair(&m_a, &m_b) {
+        Ok((a, b)) => Ok(NativeResult::ok(cost, smallvec![Value::bool(same_sign(a, b))])),
+        Err(abort_code) => Ok(NativeResult::err(cost, abort_code)),
+    }
 }
 
-#[test]
-fn sanity() {
+fn same_sign(a: Decimal, b: Decimal) -> bool {
+    let positive = |dec: Decimal| dec.is_zero() || dec.is_sign_positive();
+    positive(a) == positive(b)
+}
 
-  // let d = Decimal::new(1, 1);
-  // let d = Decimal::MAX;
-  let two = Decimal::from_i128_with_scale(2, 0);
+/// Rust implementation of Move's `native public fun scale_at_most(sign: bool, int: u128,
+/// scale: u8, max_scale: u8): bool`
+/// Whether the value has at most `max_scale` decimal places once trailing zeros are dropped,
+/// so `(true, 150, 2)`, i.e. 1.50, has one.
+pub fn native_scale_at_most(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 4);
 
-  let neg_one = Decimal::from_i128_with_scale(-1, 0);
-  let _res = two.checked_mul(neg_one).unwrap();
-  // dbg!(&res);
-  // dbg!(&res.mantissa());
+    // pop arguments in reverse order
+    let max_scale = pop_arg!(arguments, u8);
+    let scale = pop_arg!(arguments, u8);
+    let int = pop_arg!(arguments, u128);
+    let sign = pop_arg!(arguments, bool);
+    let m = MoveDecimalType::new(scale, int, sign);
+
+    let cost = decimal_cost(context.cost_table(), &m, 1)?;
+
+    match m.into_decimal() {
+        Ok(dec) => Ok(NativeResult::ok(
+            cost,
+            smallvec![Value::bool(scale_at_most(dec, max_scale))],
+        )),
+        Err(abort_code) => Ok(NativeResult::err(cost, abort_code)),
+    }
 }
 
-#[test]
-fn test_irrational() {
-    let m = MoveDecimalType {
-        sign: true,
-        int: 3,
-        scale: 0,
+fn scale_at_most(dec: Decimal, max_scale: u8) -> bool {
+    dec.normalize().scale() <= max_scale as u32
+}
+
+/// Rust implementation of Move's `native public fun twap_update(acc: vector<u8>,
+/// price_sign: bool, price_int: u128, price_scale: u8, elapsed: u64): vector<u8>`
+/// Returns the accumulator advanced by `price * elapsed`. The accumulator is the 16 byte
+/// `Decimal` encoding of the running sum, and an empty vector starts it from zero.
+/// Aborts with `EDECIMAL_MALFORMED_ACCUMULATOR` for any other encoding and with
+/// `EDECIMAL_OVERFLOW` if the sum does not fit.
+pub fn native_twap_update(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 5);
+
+    // pop arguments in reverse order
+    let elapsed = pop_arg!(arguments, u64);
+    let price_scale = pop_arg!(arguments, u8);
+    let price_int = pop_arg!(arguments, u128);
+    let price_sign = pop_arg!(arguments, bool);
+    let m_price = MoveDecimalType::new(price_scale, price_int, price_sign);
+    let acc = pop_arg!(arguments, Vec<u8>);
+
+    // a multiplication and an addition
+    let cost = decimal_cost(context.cost_table(), &m_price, pair_op_weight(3) + 1)?;
+
+    match m_price.into_decimal().and_then(|price| twap_update(&acc, price, elapsed)) {
+        Ok(acc) => Ok(NativeResult::ok(cost, smallvec![Value::vector_u8(acc)])),
+        Err(abort_code) => Ok(NativeResult::err(cost, abort_code)),
+    }
+}
+
+fn twap_update(acc: &[u8], price: Decimal, elapsed: u64) -> Result<Vec<u8>, u64> {
+    let acc = match acc.len() {
+        0 => Decimal::ZERO,
+        16 => {
+            let mut bytes = [0u8; 16];
+            bytes.copy_from_slice(acc);
+            Decimal::deserialize(bytes)
+        }
+        _ => return Err(EDECIMAL_MALFORMED_ACCUMULATOR),
+    };
+    let weighted = checked(price.checked_mul(Decimal::from(elapsed)))?;
+    let acc = checked(acc.checked_add(weighted))?;
+    Ok(acc.serialize().to_vec())
+}
+
+/// Rust implementation of Move's `native public fun present_value(future_sign: bool,
+/// future_int: u128, future_scale: u8, rate_sign: bool, rate_int: u128, rate_scale: u8,
+/// periods: u64): (bool, bool, u128, u8)`
+/// Returns `future / (1 + rate)^periods`, after a success flag which is false, with a zero
+/// result, when `rate <= -1` or the computation overflows.
+/// Aborts with `EDECIMAL_INVALID_DOMAIN` when `periods` is above `MAX_PRESENT_VALUE_PERIODS`.
+pub fn native_present_value(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 7);
+
+    // pop arguments in reverse order
+    let periods = pop_arg!(arguments, u64);
+    let rate_scale = pop_arg!(arguments, u8);
+    let rate_int = pop_arg!(arguments, u128);
+    let rate_sign = pop_arg!(arguments, bool);
+    let m_rate = MoveDecimalType::new(rate_scale, rate_int, rate_sign);
+
+    let future_scale = pop_arg!(arguments, u8);
+    let future_int = pop_arg!(arguments, u128);
+    let future_sign = pop_arg!(arguments, bool);
+    let m_future = MoveDecimalType::new(future_scale, future_int, future_sign);
+
+    // a square and a multiplication per bit of `periods`, then a division
+    let squarings = (64 - periods.leading_zeros()) as usize;
+    let cost = decimal_cost(
+        context.cost_table(),
+        &m_future,
+        2 * pair_op_weight(3) * squarings + pair_op_weight(4),
+    )?;
+
+    let (future, rate) = match into_decimal_pair(&m_future, &m_rate) {
+        Ok(pair) => pair,
+        Err(abort_code) => return Ok(NativeResult::err(cost, abort_code)),
     };
 
-    let dec = m.into_decimal();
-    // dbg!(&dec.to_string());
+    let present = match present_value(future, rate, periods) {
+        Ok(present) => present.and_then(MoveDecimalType::from_decimal),
+        Err(abort_code) => return Ok(NativeResult::err(cost, abort_code)),
+    };
+    let (ok, out) = match present {
+        Some(out) => (true, out),
+        None => (false, MoveDecimalType::new(0, 0, true)),
+    };
 
-    let i = dec.sqrt().unwrap().normalize();
-    // dbg!(&i.to_string());
-    // assert_eq!(dec.to_u128(), Some(m.int));
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![
+            Value::bool(ok),
+            Value::bool(out.sign),
+            Value::u128(out.int),
+            Value::u8(out.scale)
+        ],
+    ))
+}
 
-    let _new_m = MoveDecimalType::from_decimal(i);
-    // dbg!(&new_m);
-    // assert_eq!(m.int, new_m.int);
+/// Largest `periods` accepted by `present_value`, far more than any payment schedule needs.
+const MAX_PRESENT_VALUE_PERIODS: u64 = u32::MAX as u64;
+
+// The inner `None` is the false flag of `native_present_value`, the `Err` its abort code.
+fn present_value(future: Decimal, rate: Decimal, periods: u64) -> Result<Option<Decimal>, u64> {
+    if periods > MAX_PRESENT_VALUE_PERIODS {
+        return Err(EDECIMAL_INVALID_DOMAIN);
+    }
+    let growth = match Decimal::ONE.checked_add(rate) {
+        Some(growth) if growth > Decimal::ZERO => growth,
+        _ => return Ok(None),
+    };
+    Ok(checked_powu_by_squaring(growth, periods)
+        .and_then(|discount| future.checked_div(discount))
+        .map(|d| d.normalize()))
+}
 
+// `base^exp`, or `None` on overflow. Unlike `Decimal::checked_powu`, which multiplies `exp`
+// times, this does one square and at most one multiplication per bit of `exp`, and stops as
+// soon as the square overflows or rounds down to zero.
+fn checked_powu_by_squaring(base: Decimal, mut exp: u64) -> Option<Decimal> {
+    let mut result = Decimal::ONE;
+    let mut square = base;
+    loop {
+        if exp & 1 == 1 {
+            result = result.checked_mul(square)?;
+        }
+        exp >>= 1;
+        if exp == 0 {
+            return Some(result);
+        }
+        square = square.checked_mul(square)?;
+        if square.is_zero() {
+            // every remaining factor is zero too
+            return Some(Decimal::ZERO);
+        }
+    }
+}
 
-    // let new_dec = new_m.into_decimal();
-    // dbg!(&new_dec.to_string());
-    // assert_eq!(new_dec.to_u128(), Some(m.int));
+// `base^exp` for a whole `exp`, with `checked_powd`'s conventions: `0^exp` is 0 for any
+// non zero `exp` and exponents past `i64` overflow.
+fn checked_powi_by_squaring(base: Decimal, exp: Decimal) -> Option<Decimal> {
+    if exp.is_zero() {
+        return Some(Decimal::ONE);
+    }
+    if base.is_zero() {
+        return Some(Decimal::ZERO);
+    }
+    let exp = exp.to_i64()?;
+    let pow = checked_powu_by_squaring(base, exp.unsigned_abs())?;
+    if exp < 0 {
+        return Decimal::ONE.checked_div(pow);
+    }
+    Some(pow.normalize())
 }
 
-#[test]
-fn test_decimal_power() {
-    let left = MoveDecimalType::new(2, 200, true).into_decimal();
-    let right =  MoveDecimalType::new(2, 200, true).into_decimal();
+/// Rust implementation of Move's `native public fun to_string(sign: bool, int: u128,
+/// scale: u8): vector<u8>`
+/// The string keeps the scale, so `(false, 12340, 3)` renders as `-12.340`.
+pub fn native_to_string(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
 
-    let pow = right.to_f64().unwrap();
-    let res = left.powf(pow).normalize();
+    // pop arguments in reverse order
+    let scale = pop_arg!(arguments, u8);
+    let int = pop_arg!(arguments, u128);
+    let sign = pop_arg!(arguments, bool);
+    let m = MoveDecimalType::new(scale, int, sign);
 
-    let out = MoveDecimalType::from_decimal(res);
-    assert_eq!(out.int, 4);
+    let cost = decimal_cost(context.cost_table(), &m, 1)?;
+
+    let string = match m.into_decimal() {
+        Ok(dec) => dec.to_string(),
+        Err(abort_code) => return Ok(NativeResult::err(cost, abort_code)),
+    };
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(string.into_bytes())],
+    ))
+}
+
+/// Rust implementation of Move's `native public fun from_string(s: vector<u8>): (bool, u128, u8)`
+/// Aborts with `EDECIMAL_MALFORMED_STRING` if `s` is not a decimal number.
+pub fn native_from_string(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let bytes = pop_arg!(arguments, Vec<u8>);
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::DECIMAL, bytes.len())?;
+
+    let out = match from_string(&bytes).and_then(to_move_decimal) {
+        Ok(out) => out,
+        Err(abort_code) => return Ok(NativeResult::err(cost, abort_code)),
+    };
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![
+            Value::bool(out.sign),
+            Value::u128(out.int),
+            Value::u8(out.scale)
+        ],
+    ))
+}
+
+fn from_string(bytes: &[u8]) -> Result<Decimal, u64> {
+    std::str::from_utf8(bytes)
+        .ok()
+        .and_then(|s| Decimal::from_str(s).ok())
+        .ok_or(EDECIMAL_MALFORMED_STRING)
+}
+
+/// Rust implementation of Move's `native public fun check_sum_equals(signs: vector<bool>,
+/// ints: vector<u128>, scales: vector<u8>, total_sign: bool, total_int: u128,
+/// total_scale: u8): bool`
+/// The parts are given as parallel vectors, part `i` being `(signs[i], ints[i], scales[i])`.
+pub fn native_check_sum_equals(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 6);
+
+    // pop arguments in reverse order
+    let total_scale = pop_arg!(arguments, u8);
+    let total_int = pop_arg!(arguments, u128);
+    let total_sign = pop_arg!(arguments, bool);
+    let m_total = MoveDecimalType::new(total_scale, total_int, total_sign);
+
+    let scales = pop_arg!(arguments, Vec<u8>);
+    let ints = pop_arg!(arguments, Vec<u128>);
+    let signs = pop_arg!(arguments, Vec<bool>);
+
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::DECIMAL,
+        (ints.len() + 1) * significant_bytes(m_total.int),
+    )?;
+
+    if signs.len() != ints.len() || ints.len() != scales.len() {
+        return Ok(NativeResult::err(cost, EDECIMAL_LENGTH_MISMATCH));
+    }
+
+    let parts = signs
+        .into_iter()
+        .zip(ints)
+        .zip(scales)
+        .map(|((sign, int), scale)| MoveDecimalType::new(scale, int, sign).into_decimal())
+        .collect::<Result<Vec<_>, _>>();
+
+    match parts.and_then(|parts| check_sum_equals(parts, m_total.into_decimal()?)) {
+        Ok(equal) => Ok(NativeResult::ok(cost, smallvec![Value::bool(equal)])),
+        Err(abort_code) => Ok(NativeResult::err(cost, abort_code)),
+    }
+}
+
+// Sums the parts exactly, aborting with `EDECIMAL_OVERFLOW` rather than losing precision.
+fn check_sum_equals(
+    parts: impl IntoIterator<Item = Decimal>,
+    total: Decimal,
+) -> Result<bool, u64> {
+    let mut sum = Decimal::ZERO;
+    for part in parts {
+        sum = sum.checked_add(part).ok_or(EDECIMAL_OVERFLOW)?;
+    }
+    Ok(sum == total)
+}
+
+/// Rust implementation of Move's `native public fun cmp(sign_left: bool, int_left: u128,
+/// scale_left: u8, sign_right: bool, int_right: u128, scale_right: u8): u8`
+/// Returns 0 if left is less than right, 1 if they are equal and 2 if left is greater.
+pub fn native_cmp(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 6);
+
+    // pop arguments in reverse order
+    let scale_right = pop_arg!(arguments, u8);
+    let int_right = pop_arg!(arguments, u128);
+    let sign_right = pop_arg!(arguments, bool);
+    let m_right = MoveDecimalType::new(scale_right, int_right, sign_right);
+
+    let scale_left = pop_arg!(arguments, u8);
+    let int_left = pop_arg!(arguments, u128);
+    let sign_left = pop_arg!(arguments, bool);
+    let m_left = MoveDecimalType::new(scale_left, int_left, sign_left);
+
+    let cost = decimal_cost(context.cost_table(), &m_left, 1)?;
+
+    match into_decimal_pair(&m_left, &m_right) {
+        Ok((left, right)) => Ok(NativeResult::ok(cost, smallvec![Value::u8(cmp(left, right))])),
+        Err(abort_code) => Ok(NativeResult::err(cost, abort_code)),
+    }
+}
+
+/// Rust implementation of Move's `native public fun compare(op_id: u8, sign_left: bool,
+/// int_left: u128, scale_left: u8, sign_right: bool, int_right: u128, scale_right: u8): bool`
+/// `op_id` selects the operator: 0 `<`, 1 `==`, 2 `>`, 3 `<=`, 4 `>=`, so that for 0 to 2 the
+/// result is `cmp(left, right) == op_id`. Other values abort with `EDECIMAL_INVALID_DOMAIN`.
+pub fn native_compare(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 7);
+
+    // pop arguments in reverse order
+    let scale_right = pop_arg!(arguments, u8);
+    let int_right = pop_arg!(arguments, u128);
+    let sign_right = pop_arg!(arguments, bool);
+    let m_right = MoveDecimalType::new(scale_right, int_right, sign_right);
+
+    let scale_left = pop_arg!(arguments, u8);
+    let int_left = pop_arg!(arguments, u128);
+    let sign_left = pop_arg!(arguments, bool);
+    let m_left = MoveDecimalType::new(scale_left, int_left, sign_left);
+
+    let op_id = pop_arg!(arguments, u8);
+
+    let cost = decimal_cost(context.cost_table(), &m_left, 1)?;
+
+    let (left, right) = match into_decimal_pair(&m_left, &m_right) {
+        Ok(pair) => pair,
+        Err(abort_code) => return Ok(NativeResult::err(cost, abort_code)),
+    };
+
+    match compare(op_id, left, right) {
+        Ok(result) => Ok(NativeResult::ok(cost, smallvec![Value::bool(result)])),
+        Err(abort_code) => Ok(NativeResult::err(cost, abort_code)),
+    }
+}
+
+/// `cmp` result when the left value is the smaller one.
+const CMP_LESS: u8 = 0;
+/// `cmp` result when the values are equal.
+const CMP_EQUAL: u8 = 1;
+/// `cmp` result when the left value is the larger one.
+const CMP_GREATER: u8 = 2;
+
+// Compares the values, not the representations, so 1.0 and 1.00 are equal.
+fn cmp(left: Decimal, right: Decimal) -> u8 {
+    match left.cmp(&right) {
+        Ordering::Less => CMP_LESS,
+        Ordering::Equal => CMP_EQUAL,
+        Ordering::Greater => CMP_GREATER,
+    }
+}
+
+// The operators of `compare`, in terms of `cmp`. The `Err` is the abort code returned to Move.
+fn compare(op_id: u8, left: Decimal, right: Decimal) -> Result<bool, u64> {
+    let ordering = cmp(left, right);
+    match op_id {
+        CMP_LESS | CMP_EQUAL | CMP_GREATER => Ok(ordering == op_id),
+        3 => Ok(ordering != CMP_GREATER),
+        4 => Ok(ordering != CMP_LESS),
+        _ => Err(EDECIMAL_INVALID_DOMAIN),
+    }
+}
+
+// Gas for a decimal native: the `DECIMAL` unit cost per significant byte of the mantissa,
+// times `op_weight` for operations doing more work than a single addition.
+fn decimal_cost(
+    cost_table: &CostTable,
+    m: &MoveDecimalType,
+    op_weight: usize,
+) -> PartialVMResult<InternalGasUnits<GasCarrier>> {
+    native_gas(cost_table, NativeCostIndex::DECIMAL, significant_bytes(m.int) * op_weight)
+}
+
+// Bytes of `int` without its leading zero bytes, 0 for 0.
+fn significant_bytes(int: u128) -> usize {
+    16 - int.leading_zeros() as usize / 8
+}
+
+// Relative cost of the `pair` operations. A fractional `powf` exponent goes through `ln`
+// and `exp` series, so it is charged well above the arithmetic operations.
+fn pair_op_weight(op_id: u8) -> usize {
+    match op_id {
+        3 | 4 | 7 => 2,
+        5 => 8,
+        _ => 1,
+    }
+}
+
+// Weight of `powf` with exponent `m_exp`: a whole exponent is raised to by squaring, one
+// square and at most one multiplication per bit of its whole part.
+fn pow_weight(m_exp: &MoveDecimalType) -> usize {
+    let whole = match 10u128.checked_pow(m_exp.scale as u32) {
+        Some(unit) => m_exp.int / unit,
+        None => 0,
+    };
+    let bits = (128 - whole.leading_zeros()) as usize;
+    pair_op_weight(5) + 2 * pair_op_weight(3) * bits
+}
+
+// The inner `Err` carries the abort code returned to Move.
+fn single_op(op_id: u8, dec: Decimal) -> PartialVMResult<Result<Decimal, u64>> {
+    let result = match op_id {
+        100 => dec.sqrt().map(|d| d.normalize()).ok_or(EDECIMAL_INVALID_DOMAIN),
+        101 => Ok(dec.trunc()),
+        102 => dec.checked_ln().map(|d| d.normalize()).ok_or(EDECIMAL_INVALID_DOMAIN),
+        103 => dec.checked_log10().map(|d| d.normalize()).ok_or(EDECIMAL_INVALID_DOMAIN),
+        104 => checked(dec.checked_exp()),
+        105 => Ok(dec.floor().normalize()),
+        106 => Ok(dec.ceil().normalize()),
+        107 => Ok(dec.abs().normalize()),
+        // rounds half-way cases to the nearest even integer
+        108 => Ok(dec.round().normalize()),
+        _ => return Err(PartialVMError::new(StatusCode::INDEX_OUT_OF_BOUNDS)),
+    };
+    Ok(result)
+}
+
+// The inner `Err` carries the abort code returned to Move, so that overflow,
+// division by zero and out of range arguments never panic the VM.
+fn pair_op(
+    op_id: u8,
+    mut dec_left: Decimal,
+    dec_right: Decimal,
+    strategy: RoundingStrategy,
+) -> PartialVMResult<Result<Decimal, u64>> {
+    let result = match op_id {
+        0 => match dec_right.trunc().to_u32() {
+            Some(scale) => {
+                dec_left.rescale(scale);
+                Ok(dec_left)
+            }
+            None => Err(EDECIMAL_INVALID_DOMAIN),
+        },
+        1 => checked(dec_left.checked_add(dec_right)),
+        2 => checked(dec_left.checked_sub(dec_right)),
+        3 => checked(dec_left.checked_mul(dec_right)),
+        4 if dec_right.is_zero() => Err(EDECIMAL_DIVIDE_BY_ZERO),
+        4 => checked(dec_left.checked_div(dec_right)),
+        // `checked_powf` multiplies once per unit of a whole exponent
+        5 if dec_right.fract().is_zero() => checked(checked_powi_by_squaring(dec_left, dec_right)),
+        5 => match dec_right.to_f64() {
+            Some(pow) => checked(dec_left.checked_powf(pow)),
+            None => Err(EDECIMAL_INVALID_DOMAIN),
+        },
+        6 => match dec_right.trunc().to_u32() {
+            Some(dp) => Ok(dec_left.round_dp_with_strategy(dp, strategy)),
+            None => Err(EDECIMAL_INVALID_DOMAIN),
+        },
+        7 if dec_right.is_zero() => Err(EDECIMAL_DIVIDE_BY_ZERO),
+        7 => checked(dec_left.checked_rem(dec_right)),
+        _ => return Err(PartialVMError::new(StatusCode::INDEX_OUT_OF_BOUNDS)),
+    };
+    Ok(result)
+}
+
+// Normalizes the result of a `checked_*` operation, mapping `None` to an overflow.
+fn checked(result: Option<Decimal>) -> Result<Decimal, u64> {
+    result.map(|d| d.normalize()).ok_or(EDECIMAL_OVERFLOW)
+}
+
+fn to_move_decimal(dec: Decimal) -> Result<MoveDecimalType, u64> {
+    MoveDecimalType::from_decimal(dec).ok_or(EDECIMAL_OVERFLOW)
+}
+
+fn into_decimal_pair(
+    left: &MoveDecimalType,
+    right: &MoveDecimalType,
+) -> Result<(Decimal, Decimal), u64> {
+    Ok((left.into_decimal()?, right.into_decimal()?))
+}
+
+#[test]
+fn test_into_dec() {
+    let m = MoveDecimalType {
+        sign: true,
+        int: Decimal::MAX.to_u128().unwrap(), //79228162514264337593543950335
+        scale: 0,
+    };
+
+    let dec = m.into_decimal().unwrap();
+
+    // dbg!(&dec.to_string());
+    assert_eq!(dec.to_u128(), Some(m.int));
+
+    let new_m = MoveDecimalType::from_decimal(dec).unwrap();
+    // dbg!(&new_m);
+    assert_eq!(m.int, new_m.int);
+
+
+    let new_dec = new_m.into_decimal().unwrap();
+    // dbg!(&new_dec.to_string());
+    assert_eq!(new_dec.to_u128(), Some(m.int));
+}
+
+#[test]
+fn sanity() {
+
+  // let d = Decimal::new(1, 1);
+  // let d = Decimal::MAX;
+  let two = Decimal::from_i128_with_scale(2, 0);
+
+  let neg_one = Decimal::from_i128_with_scale(-1, 0);
+  let _res = two.checked_mul(neg_one).unwrap();
+  // dbg!(&res);
+  // dbg!(&res.mantissa());
+}
+
+#[test]
+fn test_irrational() {
+    let m = MoveDecimalType {
+        sign: true,
+        int: 3,
+        scale: 0,
+    };
+
+    let dec = m.into_decimal().unwrap();
+    // dbg!(&dec.to_string());
+
+    let i = dec.sqrt().unwrap().normalize();
+    // dbg!(&i.to_string());
+    // assert_eq!(dec.to_u128(), Some(m.int));
+
+    let _new_m = MoveDecimalType::from_decimal(i).unwrap();
+    // dbg!(&new_m);
+    // assert_eq!(m.int, new_m.int);
+
+
+    // let new_dec = new_m.into_decimal().unwrap();
+    // dbg!(&new_dec.to_string());
+    // assert_eq!(new_dec.to_u128(), Some(m.int));
+}
+
+#[test]
+fn test_decimal_power() {
+    let left = MoveDecimalType::new(2, 200, true).into_decimal().unwrap();
+    let right =  MoveDecimalType::new(2, 200, true).into_decimal().unwrap();
+
+    let pow = right.to_f64().unwrap();
+    let res = left.powf(pow).normalize();
+
+    let out = MoveDecimalType::from_decimal(res).unwrap();
+    assert_eq!(out.int, 4);
+}
+
+#[test]
+fn test_pair_mul_overflow() {
+    let left = MoveDecimalType::new(0, Decimal::MAX.to_u128().unwrap(), true)
+        .into_decimal()
+        .unwrap();
+    let right = MoveDecimalType::new(0, 2, true).into_decimal().unwrap();
+
+    let res = pair_op(3, left, right, RoundingStrategy::MidpointNearestEven).unwrap();
+    assert_eq!(res, Err(EDECIMAL_OVERFLOW));
+}
+
+#[test]
+fn test_pair_div_by_zero() {
+    let left = MoveDecimalType::new(0, 1, true).into_decimal().unwrap();
+    let right = MoveDecimalType::new(0, 0, true).into_decimal().unwrap();
+
+    let res = pair_op(4, left, right, RoundingStrategy::MidpointNearestEven).unwrap();
+    assert_eq!(res, Err(EDECIMAL_DIVIDE_BY_ZERO));
+}
+
+#[test]
+fn test_single_logarithms() {
+    let ln_e = single_op(102, Decimal::E).unwrap().unwrap();
+    assert!((ln_e - Decimal::ONE).abs() < Decimal::new(1, 20));
+
+    let thousand = MoveDecimalType::new(0, 1000, true).into_decimal().unwrap();
+    let log = single_op(103, thousand).unwrap().unwrap();
+    assert_eq!(MoveDecimalType::from_decimal(log).unwrap().int, 3);
+}
+
+#[test]
+fn test_single_logarithm_domain() {
+    let zero = MoveDecimalType::new(0, 0, true).into_decimal().unwrap();
+    let negative = MoveDecimalType::new(0, 5, false).into_decimal().unwrap();
+
+    assert_eq!(single_op(102, zero).unwrap(), Err(EDECIMAL_INVALID_DOMAIN));
+    assert_eq!(single_op(102, negative).unwrap(), Err(EDECIMAL_INVALID_DOMAIN));
+    assert_eq!(single_op(103, zero).unwrap(), Err(EDECIMAL_INVALID_DOMAIN));
+    assert_eq!(single_op(103, negative).unwrap(), Err(EDECIMAL_INVALID_DOMAIN));
+}
+
+#[test]
+fn test_single_exp() {
+    // the series stops once a term is below rust_decimal's exp tolerance of about 2e-7
+    let e = single_op(104, Decimal::ONE).unwrap().unwrap();
+    assert!((e - Decimal::E).abs() < Decimal::new(1, 6));
+
+    // exp is the inverse of ln
+    let seven = MoveDecimalType::new(0, 7, true).into_decimal().unwrap();
+    let ln_seven = single_op(102, seven).unwrap().unwrap();
+    let round_trip = single_op(104, ln_seven).unwrap().unwrap();
+    assert!((round_trip - seven).abs() < Decimal::new(1, 5));
+
+    // e^100 does not fit in a Decimal
+    let hundred = MoveDecimalType::new(0, 100, true).into_decimal().unwrap();
+    assert_eq!(single_op(104, hundred).unwrap(), Err(EDECIMAL_OVERFLOW));
+}
+
+#[test]
+fn test_cmp() {
+    let one = MoveDecimalType::new(1, 10, true).into_decimal().unwrap();
+    let one_hundredths = MoveDecimalType::new(2, 100, true).into_decimal().unwrap();
+    assert_eq!(cmp(one, one_hundredths), 1);
+
+    let half = MoveDecimalType::new(1, 5, true).into_decimal().unwrap();
+    assert_eq!(cmp(half, one), 0);
+    assert_eq!(cmp(one, half), 2);
+
+    let minus_one = MoveDecimalType::new(0, 1, false).into_decimal().unwrap();
+    assert_eq!(cmp(minus_one, half), 0);
+
+    // extreme scales do not lose precision
+    let tiny = MoveDecimalType::new(28, 1, true).into_decimal().unwrap();
+    let zero = MoveDecimalType::new(0, 0, true).into_decimal().unwrap();
+    assert_eq!(cmp(tiny, zero), 2);
+}
+
+#[test]
+fn test_compare() {
+    let one = MoveDecimalType::new(1, 10, true).into_decimal().unwrap();
+    let one_hundredths = MoveDecimalType::new(2, 100, true).into_decimal().unwrap();
+    assert!(compare(1, one, one_hundredths).unwrap());
+    assert!(compare(3, one, one_hundredths).unwrap());
+    assert!(compare(4, one, one_hundredths).unwrap());
+    assert!(!compare(0, one, one_hundredths).unwrap());
+    assert!(!compare(2, one, one_hundredths).unwrap());
+
+    let minus_two = MoveDecimalType::new(0, 2, false).into_decimal().unwrap();
+    assert!(compare(0, minus_two, one).unwrap());
+    assert!(compare(2, one, minus_two).unwrap());
+
+    assert_eq!(compare(5, one, one), Err(EDECIMAL_INVALID_DOMAIN));
+    assert_eq!(compare(u8::MAX, one, one), Err(EDECIMAL_INVALID_DOMAIN));
+    // the first three operators test for the matching `cmp` result
+    for op_id in CMP_LESS..=CMP_GREATER {
+        assert_eq!(compare(op_id, one, minus_two), Ok(cmp(one, minus_two) == op_id));
+        assert_eq!(compare(op_id, minus_two, one), Ok(cmp(minus_two, one) == op_id));
+    }
+}
+
+#[test]
+fn test_compare_negative_zero() {
+    let zero = MoveDecimalType::new(0, 0, true).into_decimal().unwrap();
+    let minus_zero = MoveDecimalType::new(3, 0, false).into_decimal().unwrap();
+    assert!(compare(1, zero, minus_zero).unwrap());
+
+    let mut signed_zero = Decimal::ZERO;
+    signed_zero.set_sign_negative(true);
+    assert!(compare(1, zero, signed_zero).unwrap());
+    assert!(!compare(0, signed_zero, zero).unwrap());
+
+    let tiny = MoveDecimalType::new(28, 1, true).into_decimal().unwrap();
+    assert!(compare(0, signed_zero, tiny).unwrap());
+}
+
+#[test]
+fn test_complement_to() {
+    let value = MoveDecimalType::new(1, 3, true).into_decimal().unwrap();
+    let whole = MoveDecimalType::new(1, 10, true).into_decimal().unwrap();
+    let out = MoveDecimalType::from_decimal(complement_to(value, whole).unwrap()).unwrap();
+    assert!(out.sign);
+    assert_eq!((out.int, out.scale), (7, 1));
+
+    // a value above the boundary gives a negative complement
+    let over = MoveDecimalType::new(2, 125, true).into_decimal().unwrap();
+    let out = MoveDecimalType::from_decimal(complement_to(over, whole).unwrap()).unwrap();
+    assert!(!out.sign);
+    assert_eq!((out.int, out.scale), (25, 2));
+
+    let max = MoveDecimalType::new(0, Decimal::MAX.to_u128().unwrap(), true)
+        .into_decimal()
+        .unwrap();
+    let min = MoveDecimalType::new(0, Decimal::MAX.to_u128().unwrap(), false)
+        .into_decimal()
+        .unwrap();
+    assert_eq!(complement_to(max, min), Err(EDECIMAL_OVERFLOW));
+}
+
+#[test]
+fn test_sign_round_trip() {
+    let operands: Vec<Decimal> = [(0, 0), (5, 0), (-5, 0), (3, 1), (-3, 1), (-250, 2), (7, 3)]
+        .iter()
+        .map(|&(int, scale)| Decimal::from_i128_with_scale(int, scale))
+        .collect();
+
+    for &left in &operands {
+        for &right in &operands {
+            for op_id in [1, 2] {
+                let expected = pair_op(op_id, left, right, RoundingStrategy::MidpointNearestEven)
+                    .unwrap()
+                    .unwrap();
+                let m = MoveDecimalType::from_decimal(expected).unwrap();
+                assert_eq!(m.sign, !expected.is_sign_negative() || expected.is_zero());
+                let round_trip = m.into_decimal().unwrap();
+                assert_eq!(round_trip, expected);
+                assert_eq!(round_trip.to_string(), expected.to_string());
+            }
+        }
+    }
+
+    let zero_minus_five = pair_op(
+        2,
+        operands[0],
+        operands[1],
+        RoundingStrategy::MidpointNearestEven,
+    )
+    .unwrap()
+    .unwrap();
+    assert!(!MoveDecimalType::from_decimal(zero_minus_five).unwrap().sign);
+
+    let mut negative_zero = Decimal::ZERO;
+    negative_zero.set_sign_negative(true);
+    assert!(MoveDecimalType::from_decimal(negative_zero).unwrap().sign);
+}
+
+#[test]
+fn test_pair_rem() {
+    let strategy = RoundingStrategy::MidpointNearestEven;
+    let five_and_half = MoveDecimalType::new(1, 55, true).into_decimal().unwrap();
+    let two = MoveDecimalType::new(0, 2, true).into_decimal().unwrap();
+
+    let out =
+        to_move_decimal(pair_op(7, five_and_half, two, strategy).unwrap().unwrap()).unwrap();
+    assert!(out.sign);
+    assert_eq!((out.int, out.scale), (15, 1));
+
+    // the remainder takes the sign of the dividend
+    let minus_five_and_half = MoveDecimalType::new(1, 55, false).into_decimal().unwrap();
+    let out = MoveDecimalType::from_decimal(
+        pair_op(7, minus_five_and_half, two, strategy).unwrap().unwrap(),
+    ).unwrap();
+    assert!(!out.sign);
+    assert_eq!((out.int, out.scale), (15, 1));
+
+    let zero = MoveDecimalType::new(0, 0, true).into_decimal().unwrap();
+    assert_eq!(
+        pair_op(7, five_and_half, zero, strategy).unwrap(),
+        Err(EDECIMAL_DIVIDE_BY_ZERO)
+    );
+}
+
+#[test]
+fn test_check_sum_equals() {
+    let parts = [
+        MoveDecimalType::new(1, 3, true).into_decimal().unwrap(),
+        MoveDecimalType::new(2, 45, true).into_decimal().unwrap(),
+        MoveDecimalType::new(0, 2, false).into_decimal().unwrap(),
+    ];
+    // 0.3 + 0.45 - 2 = -1.25
+    let total = MoveDecimalType::new(3, 1250, false).into_decimal().unwrap();
+    assert_eq!(check_sum_equals(parts, total), Ok(true));
+
+    let off_by_one = MoveDecimalType::new(3, 1251, false).into_decimal().unwrap();
+    assert_eq!(check_sum_equals(parts, off_by_one), Ok(false));
+
+    assert_eq!(check_sum_equals(Vec::new(), Decimal::ZERO), Ok(true));
+
+    let max = MoveDecimalType::new(0, Decimal::MAX.to_u128().unwrap(), true)
+        .into_decimal()
+        .unwrap();
+    assert_eq!(check_sum_equals([max, max], max), Err(EDECIMAL_OVERFLOW));
+}
+
+#[test]
+fn test_abs_diff() {
+    let a = MoveDecimalType::new(2, 314, true).into_decimal().unwrap();
+    let same = MoveDecimalType::new(3, 3140, true).into_decimal().unwrap();
+    assert_eq!(abs_diff(a, same), Ok(Decimal::ZERO));
+    assert!(MoveDecimalType::from_decimal(abs_diff(a, same).unwrap()).unwrap().sign);
+
+    // |3.14 - 3.14159| = |3.14159 - 3.14| = 0.00159
+    let pi = MoveDecimalType::new(5, 314159, true).into_decimal().unwrap();
+    for diff in [abs_diff(a, pi).unwrap(), abs_diff(pi, a).unwrap()] {
+        let out = MoveDecimalType::from_decimal(diff).unwrap();
+        assert!(out.sign);
+        assert_eq!((out.int, out.scale), (159, 5));
+    }
+
+    let max = MoveDecimalType::new(0, Decimal::MAX.to_u128().unwrap(), true)
+        .into_decimal()
+        .unwrap();
+    let min = MoveDecimalType::new(0, Decimal::MAX.to_u128().unwrap(), false)
+        .into_decimal()
+        .unwrap();
+    assert_eq!(abs_diff(max, min), Err(EDECIMAL_OVERFLOW));
+}
+
+#[test]
+fn test_string_round_trip() {
+    let m = MoveDecimalType::new(3, 12340, false);
+    let string = m.into_decimal().unwrap().to_string();
+    assert_eq!(string, "-12.340");
+
+    let out = MoveDecimalType::from_decimal(from_string(string.as_bytes()).unwrap()).unwrap();
+    assert_eq!((out.sign, out.int, out.scale), (false, 12340, 3));
+
+    // leading zeros are dropped, trailing zeros keep the scale
+    let out = MoveDecimalType::from_decimal(from_string(b"007.500").unwrap()).unwrap();
+    assert_eq!((out.sign, out.int, out.scale), (true, 7500, 3));
+    assert_eq!(from_string(b"0.0").unwrap().to_string(), "0.0");
+}
+
+#[test]
+fn test_from_string_malformed() {
+    for malformed in [&b"1.2.3"[..], b"", b"abc", b"1e", &[0xff, 0xfe]] {
+        assert_eq!(from_string(malformed), Err(EDECIMAL_MALFORMED_STRING));
+    }
+}
+
+#[test]
+fn test_single_floor_ceil_abs() {
+    let minus_one_and_half = MoveDecimalType::new(1, 15, false).into_decimal().unwrap();
+
+    let floor = to_move_decimal(single_op(105, minus_one_and_half).unwrap().unwrap()).unwrap();
+    assert_eq!((floor.sign, floor.int, floor.scale), (false, 2, 0));
+
+    let ceil = to_move_decimal(single_op(106, minus_one_and_half).unwrap().unwrap()).unwrap();
+    assert_eq!((ceil.sign, ceil.int, ceil.scale), (false, 1, 0));
+
+    let minus_three_and_fifth = MoveDecimalType::new(1, 32, false).into_decimal().unwrap();
+    let abs = single_op(107, minus_three_and_fifth).unwrap().unwrap();
+    let abs = MoveDecimalType::from_decimal(abs).unwrap();
+    assert_eq!((abs.sign, abs.int, abs.scale), (true, 32, 1));
+
+    // ceil of a small negative value is zero, which Move always sees as positive
+    let minus_half = MoveDecimalType::new(1, 5, false).into_decimal().unwrap();
+    let ceil = MoveDecimalType::from_decimal(single_op(106, minus_half).unwrap().unwrap()).unwrap();
+    assert_eq!((ceil.sign, ceil.int), (true, 0));
+}
+
+#[test]
+fn test_convert() {
+    let rate = Decimal::new(125, 2);
+    assert_eq!(convert(100, rate, false), Ok(125));
+    assert_eq!(convert(125, rate, true), Ok(100));
+    // both directions round down
+    assert_eq!(convert(3, rate, false), Ok(3));
+    assert_eq!(convert(3, rate, true), Ok(2));
+    assert_eq!(convert(0, rate, true), Ok(0));
+
+    assert_eq!(convert(100, Decimal::ZERO, false), Ok(0));
+    assert_eq!(convert(100, Decimal::ZERO, true), Err(EDECIMAL_DIVIDE_BY_ZERO));
+    assert_eq!(convert(100, Decimal::new(-125, 2), false), Err(EDECIMAL_INVALID_DOMAIN));
+    // amounts beyond the 96 bit mantissa cannot be represented
+    assert_eq!(convert(u128::MAX, rate, false), Err(EDECIMAL_OVERFLOW));
+}
+
+#[test]
+fn test_decimal_cost() {
+    use move_core_types::gas_schedule::GasAlgebra;
+    use move_vm_types::gas_schedule::INITIAL_GAS_SCHEDULE;
+
+    let m = MoveDecimalType::new(2, 12345, true);
+    let single = decimal_cost(&INITIAL_GAS_SCHEDULE, &m, 1).unwrap();
+    assert!(single.get() > 0);
+    assert_eq!(decimal_cost(&INITIAL_GAS_SCHEDULE, &m, 1).unwrap(), single);
+
+    let pair = |op_id| {
+        decimal_cost(&INITIAL_GAS_SCHEDULE, &m, pair_op_weight(op_id)).unwrap().get()
+    };
+    assert_eq!(pair(1), single.get());
+    assert!(pair(3) > pair(1));
+    assert!(pair(5) > pair(3));
+    // every op, including unknown ones, is charged
+    assert!((0..=u8::MAX).all(|op_id| pair(op_id) >= single.get()));
+
+    // only the significant bytes of the mantissa are charged
+    let cost = |int| decimal_cost(&INITIAL_GAS_SCHEDULE, &MoveDecimalType::new(0, int, true), 1);
+    assert_eq!(cost(0).unwrap(), cost(1).unwrap());
+    assert_eq!(cost(u128::MAX).unwrap().get(), cost(1).unwrap().get() * 16);
+    assert!(cost(1 << 64).unwrap().get() > cost(u64::MAX as u128).unwrap().get());
+    assert_eq!(significant_bytes(0), 0);
+    assert_eq!(significant_bytes(0xff), 1);
+    assert_eq!(significant_bytes(0x100), 2);
+    assert_eq!(significant_bytes(u128::MAX), 16);
+
+    // a whole `powf` exponent is charged by its size, a fractional one at the flat weight
+    let pow = |int, scale| pow_weight(&MoveDecimalType::new(scale, int, true));
+    assert_eq!(pow(5, 1), pair_op_weight(5));
+    assert!(pow(2, 0) < pow(u32::MAX as u128, 0));
+    assert_eq!(pow(u32::MAX as u128, 0), pair_op_weight(5) + 2 * pair_op_weight(3) * 32);
+    assert_eq!(pow(u32::MAX as u128 * 100, 2), pow(u32::MAX as u128, 0));
+    assert_eq!(pow(u128::MAX, u8::MAX), pair_op_weight(5));
+}
+
+#[test]
+fn test_pair_pow_large_exponent() {
+    let dec = Decimal::new;
+    let pow = |base, exp| pair_op(5, base, exp, RoundingStrategy::MidpointNearestEven).unwrap();
+    let big = Decimal::from(u32::MAX);
+
+    // these took one multiplication per unit of the exponent
+    assert_eq!(pow(dec(10_000_001, 7), big), Err(EDECIMAL_OVERFLOW));
+    assert_eq!(pow(dec(9_999_999, 7), big), Ok(Decimal::ZERO));
+    assert_eq!(pow(Decimal::ONE, Decimal::from(i64::MAX)), Ok(Decimal::ONE));
+    assert_eq!(pow(dec(2, 0), Decimal::from(u64::MAX)), Err(EDECIMAL_OVERFLOW));
+
+    assert_eq!(pow(dec(2, 0), dec(10, 0)), Ok(dec(1024, 0)));
+    assert_eq!(pow(dec(2, 0), dec(-2, 0)), Ok(dec(25, 2)));
+    assert_eq!(pow(dec(15, 1), dec(20, 1)), Ok(dec(225, 2)));
+    assert_eq!(pow(Decimal::ZERO, dec(-1, 0)), Ok(Decimal::ZERO));
+    assert_eq!(pow(dec(7, 0), Decimal::ZERO), Ok(Decimal::ONE));
+}
+
+#[test]
+fn test_from_ratio() {
+    let ratio = |numerator, denominator, scale| {
+        from_ratio(numerator, denominator, scale).map(|r| r.and_then(MoveDecimalType::from_decimal))
+    };
+
+    let third = ratio(1, 3, 6).unwrap().unwrap();
+    assert_eq!((third.sign, third.int, third.scale), (true, 333333, 6));
+    let two_thirds = ratio(2, 3, 6).unwrap().unwrap();
+    assert_eq!((two_thirds.sign, two_thirds.int, two_thirds.scale), (true, 666667, 6));
+    // exact ratios are padded to the requested scale
+    let half = ratio(1, 2, 3).unwrap().unwrap();
+    assert_eq!((half.sign, half.int, half.scale), (true, 500, 3));
+    let whole = ratio(10, 5, 0).unwrap().unwrap();
+    assert_eq!((whole.sign, whole.int, whole.scale), (true, 2, 0));
+
+    assert!(ratio(1, 0, 6).unwrap().is_none());
+    assert_eq!(ratio(1, u128::MAX, 6).unwrap().unwrap().int, 0);
+    assert_eq!(ratio(u128::MAX, 1, 6).err(), Some(EDECIMAL_OVERFLOW));
+}
+
+#[test]
+fn test_same_sign() {
+    let negative_zero = MoveDecimalType::new(3, 0, false).into_decimal().unwrap();
+
+    assert!(same_sign(Decimal::new(15, 1), Decimal::new(2, 0)));
+    assert!(same_sign(Decimal::new(-15, 1), Decimal::new(-2, 0)));
+    assert!(!same_sign(Decimal::new(15, 1), Decimal::new(-2, 0)));
+    assert!(!same_sign(Decimal::new(-15, 1), Decimal::new(2, 0)));
+    // zero is positive, even with a negative sign flag
+    assert!(same_sign(Decimal::ZERO, Decimal::new(2, 0)));
+    assert!(same_sign(negative_zero, Decimal::new(2, 0)));
+    assert!(same_sign(negative_zero, Decimal::ZERO));
+    assert!(!same_sign(negative_zero, Decimal::new(-2, 0)));
+}
+
+#[test]
+fn test_single_round() {
+    let round = |scale, int, sign| {
+        let dec = MoveDecimalType::new(scale, int, sign).into_decimal().unwrap();
+        let out = MoveDecimalType::from_decimal(single_op(108, dec).unwrap().unwrap()).unwrap();
+        (out.sign, out.int, out.scale)
+    };
+
+    // positive
+    assert_eq!(round(1, 14, true), (true, 1, 0));
+    assert_eq!(round(1, 16, true), (true, 2, 0));
+    assert_eq!(round(1, 25, true), (true, 2, 0));
+    // negative
+    assert_eq!(round(1, 16, false), (false, 2, 0));
+    assert_eq!(round(1, 15, false), (false, 2, 0));
+    assert_eq!(round(1, 4, false), (true, 0, 0));
+    // exact integers are unchanged, whatever their scale
+    assert_eq!(round(0, 7, true), (true, 7, 0));
+    assert_eq!(round(2, 700, false), (false, 7, 0));
+    for op_id in [105, 106] {
+        let exact = MoveDecimalType::new(2, 700, false).into_decimal().unwrap();
+        let out = MoveDecimalType::from_decimal(single_op(op_id, exact).unwrap().unwrap()).unwrap();
+        assert_eq!((out.sign, out.int, out.scale), (false, 7, 0));
+    }
+}
+
+#[test]
+fn test_from_decimal_extremes() {
+    // the most negative mantissa converts without truncating and round-trips
+    let min = MoveDecimalType::from_decimal(Decimal::MIN).unwrap();
+    assert!(!min.sign);
+    assert_eq!(min.int, Decimal::MAX.mantissa() as u128);
+    assert_eq!(min.into_decimal().unwrap(), Decimal::MIN);
+
+    let max = MoveDecimalType::from_decimal(Decimal::MAX).unwrap();
+    assert_eq!(max.into_decimal().unwrap(), Decimal::MAX);
+
+    assert!(to_move_decimal(Decimal::MIN).is_ok());
+}
+
+#[test]
+fn test_twap_update() {
+    let decode = |acc: &[u8]| {
+        let mut bytes = [0u8; 16];
+        bytes.copy_from_slice(acc);
+        Decimal::deserialize(bytes)
+    };
+
+    // 1.25 for 10s, then 1.5 for 4s, then 0.75 for 6s
+    let acc = twap_update(&[], Decimal::new(125, 2), 10).unwrap();
+    assert_eq!(decode(&acc), Decimal::new(125, 1));
+    let acc = twap_update(&acc, Decimal::new(15, 1), 4).unwrap();
+    assert_eq!(decode(&acc), Decimal::new(185, 1));
+    let acc = twap_update(&acc, Decimal::new(75, 2), 6).unwrap();
+    assert_eq!(decode(&acc), Decimal::new(23, 0));
+    // no time elapsed leaves the sum unchanged
+    assert_eq!(decode(&twap_update(&acc, Decimal::new(99, 0), 0).unwrap()), Decimal::new(23, 0));
+
+    // the sum must fit in a `Decimal`
+    let full = Decimal::MAX.serialize();
+    assert_eq!(twap_update(&full, Decimal::ONE, 1), Err(EDECIMAL_OVERFLOW));
+    assert_eq!(twap_update(&[], Decimal::MAX, u64::MAX), Err(EDECIMAL_OVERFLOW));
+    // and the accumulator must be empty or a full encoding
+    assert_eq!(twap_update(&full[..8], Decimal::ONE, 1), Err(EDECIMAL_MALFORMED_ACCUMULATOR));
+}
+
+#[test]
+fn test_present_value() {
+    let dec = Decimal::new;
+
+    // 1000 / 1.05^2 = 1000 / 1.1025 = 907.029478...
+    let pv = present_value(dec(1000, 0), dec(5, 2), 2).unwrap().unwrap();
+    assert_eq!(pv.round_dp(6), dec(907_029_478, 6));
+    // -0.5 doubles the value each period
+    assert_eq!(present_value(dec(100, 0), dec(-5, 1), 3), Ok(Some(dec(800, 0))));
+    // no discounting without a rate or without periods
+    assert_eq!(present_value(dec(-125, 1), Decimal::ZERO, 10), Ok(Some(dec(-125, 1))));
+    assert_eq!(present_value(dec(125, 1), dec(5, 2), 0), Ok(Some(dec(125, 1))));
+
+    // a rate of -100% or below has no present value
+    assert_eq!(present_value(dec(100, 0), Decimal::NEGATIVE_ONE, 1), Ok(None));
+    assert_eq!(present_value(dec(100, 0), dec(-2, 0), 2), Ok(None));
+    // nor does a discount factor that overflows
+    assert_eq!(present_value(dec(100, 0), dec(2, 0), 1_000), Ok(None));
+    // or one that rounds down to zero
+    assert_eq!(present_value(dec(100, 0), dec(-5, 1), 1_000), Ok(None));
+}
+
+#[test]
+fn test_present_value_periods_bound() {
+    let dec = Decimal::new;
+    let max = MAX_PRESENT_VALUE_PERIODS;
+
+    // neither a zero rate, whose discount never overflows, nor a tiny negative one, whose
+    // discount never underflows, can make the native loop over `periods`
+    assert_eq!(present_value(dec(100, 0), Decimal::ZERO, u64::MAX), Err(EDECIMAL_INVALID_DOMAIN));
+    assert_eq!(present_value(dec(100, 0), dec(-1, 28), u64::MAX), Err(EDECIMAL_INVALID_DOMAIN));
+    assert_eq!(present_value(dec(100, 0), Decimal::ZERO, max), Ok(Some(dec(100, 0))));
+    // 100 / (1 - 1e-28)^(2^32 - 1) is about 100 + 4.3e-17
+    let pv = present_value(dec(100, 0), dec(-1, 28), max).unwrap().unwrap();
+    assert_eq!(pv.round_dp(16), dec(100, 0));
+    assert!(pv > dec(100, 0));
+    // (1 - 1e-9)^(2^32 - 1) = e^-4.29..., so the value grows about 73 times
+    let pv = present_value(dec(100, 0), dec(-1, 9), max).unwrap().unwrap();
+    assert_eq!(pv.round(), dec(7_333, 0));
+
+    assert_eq!(checked_powu_by_squaring(dec(3, 0), 5), Some(dec(243, 0)));
+    assert_eq!(checked_powu_by_squaring(dec(5, 1), 0), Some(Decimal::ONE));
+    assert_eq!(checked_powu_by_squaring(Decimal::ONE, u64::MAX), Some(Decimal::ONE));
+    assert_eq!(checked_powu_by_squaring(dec(2, 0), u64::MAX), None);
+    assert_eq!(checked_powu_by_squaring(dec(5, 1), u64::MAX), Some(Decimal::ZERO));
+}
+
+#[test]
+fn test_scale_at_most() {
+    let dec = |scale, int| MoveDecimalType::new(scale, int, true).into_decimal().unwrap();
+
+    // 1.50 is 1.5
+    assert!(scale_at_most(dec(2, 150), 2));
+    assert!(scale_at_most(dec(2, 150), 1));
+    assert!(!scale_at_most(dec(2, 150), 0));
+    // 1.555
+    assert!(!scale_at_most(dec(3, 1555), 2));
+    assert!(scale_at_most(dec(3, 1555), 3));
+    // integers, including zero with a scale, have none
+    assert!(scale_at_most(dec(4, 20000), 0));
+    assert!(scale_at_most(dec(6, 0), 0));
+    assert!(scale_at_most(MoveDecimalType::new(3, 1555, false).into_decimal().unwrap(), 3));
+}
+
+#[test]
+fn test_into_decimal_out_of_range() {
+    // a `Decimal` holds at most 28 decimal places
+    assert!(MoveDecimalType::new(28, 1, true).into_decimal().is_ok());
+    assert_eq!(MoveDecimalType::new(29, 1, true).into_decimal(), Err(EDECIMAL_OVERFLOW));
+    assert_eq!(MoveDecimalType::new(u8::MAX, 0, true).into_decimal(), Err(EDECIMAL_OVERFLOW));
+    // and a 96 bit mantissa
+    assert_eq!(MoveDecimalType::new(0, 1 << 100, true).into_decimal(), Err(EDECIMAL_OVERFLOW));
+    assert_eq!(MoveDecimalType::new(0, 1 << 100, false).into_decimal(), Err(EDECIMAL_OVERFLOW));
+    // mantissas past `i128::MAX` are rejected rather than wrapped to a negative number
+    assert_eq!(MoveDecimalType::new(0, 1 << 127, true).into_decimal(), Err(EDECIMAL_OVERFLOW));
+    assert_eq!(MoveDecimalType::new(0, u128::MAX, false).into_decimal(), Err(EDECIMAL_OVERFLOW));
+    // so the operations abort instead of panicking
+    let ok = MoveDecimalType::new(0, 1, true);
+    let bad = MoveDecimalType::new(29, 1, true);
+    assert_eq!(into_decimal_pair(&ok, &bad), Err(EDECIMAL_OVERFLOW));
+    assert_eq!(into_decimal_pair(&bad, &ok), Err(EDECIMAL_OVERFLOW));
 }
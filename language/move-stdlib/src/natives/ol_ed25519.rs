@@ -0,0 +1,101 @@
+//////// 0L ////////
+// SPDX-License-Identifier: Apache-2.0
+use curve25519_dalek::{
+    edwards::{CompressedEdwardsY, EdwardsPoint},
+    scalar::Scalar,
+};
+use move_binary_format::errors::PartialVMResult;
+use move_vm_runtime::native_functions::NativeContext;
+use move_vm_types::{
+    gas_schedule::NativeCostIndex,
+    loaded_data::runtime_types::Type,
+    natives::function::{native_gas, NativeResult},
+    pop_arg,
+    values::Value,
+};
+use sha2::{Digest, Sha512};
+use smallvec::smallvec;
+use std::{collections::VecDeque, convert::TryInto};
+
+/// Rust implementation of Move's `native public fun ed25519ph_verify(pubkey: vector<u8>,
+/// prehashed: vector<u8>, sig: vector<u8>, context: vector<u8>): bool`
+/// Verifies an RFC 8032 Ed25519ph signature, where `prehashed` is the SHA-512 digest of the
+/// message and `context` is at most 255 bytes (empty when the signer used none).
+/// Malformed keys, digests, signatures or contexts verify as false.
+pub fn native_ed25519ph_verify(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 4);
+
+    let ctx = pop_arg!(arguments, Vec<u8>);
+    let sig = pop_arg!(arguments, Vec<u8>);
+    let prehashed = pop_arg!(arguments, Vec<u8>);
+    let pubkey = pop_arg!(arguments, Vec<u8>);
+
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::ETH_SIGNATURE_VERIFY,
+        prehashed.len() + ctx.len(),
+    )?;
+
+    let verified = ed25519ph_verify(&pubkey, &prehashed, &sig, &ctx);
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(verified)]))
+}
+
+// Checks `[s]B == R + [k]A` with `k = SHA-512(dom2(1, ctx) || R || A || prehashed)`.
+#[allow(non_snake_case)]
+fn ed25519ph_verify(pubkey: &[u8], prehashed: &[u8], sig: &[u8], ctx: &[u8]) -> bool {
+    if prehashed.len() != 64 || sig.len() != 64 || ctx.len() > 255 {
+        return false;
+    }
+    let A = match pubkey.try_into().ok().and_then(|a| CompressedEdwardsY(a).decompress()) {
+        Some(A) => A,
+        None => return false,
+    };
+    let R = CompressedEdwardsY(sig[..32].try_into().unwrap());
+    let s = match Scalar::from_canonical_bytes(sig[32..].try_into().unwrap()) {
+        Some(s) => s,
+        None => return false,
+    };
+
+    let mut h = Sha512::new();
+    h.update(b"SigEd25519 no Ed25519 collisions");
+    h.update([1, ctx.len() as u8]);
+    h.update(ctx);
+    h.update(R.as_bytes());
+    h.update(pubkey);
+    h.update(prehashed);
+    let k = Scalar::from_hash(h);
+
+    EdwardsPoint::vartime_double_scalar_mul_basepoint(&k, &-A, &s).compress() == R
+}
+
+#[test]
+fn test_ed25519ph_verify() {
+    // RFC 8032 section 7.3, TEST abc
+    let pubkey =
+        hex::decode("ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf").unwrap();
+    let sig = hex::decode(concat!(
+        "98a70222f0b8121aa9d30f813d683f809e462b469c7ff87639499bb94e6dae41",
+        "31f85042463c2a355a2003d062adf5aaa10b8c61e636062aaad11c2a26083406",
+    ))
+    .unwrap();
+    let prehashed = Sha512::digest(b"abc").to_vec();
+
+    assert!(ed25519ph_verify(&pubkey, &prehashed, &sig, b""));
+    // the context is part of the signed data
+    assert!(!ed25519ph_verify(&pubkey, &prehashed, &sig, b"foo"));
+    assert!(!ed25519ph_verify(&pubkey, &Sha512::digest(b"abd"), &sig, b""));
+
+    // malformed input
+    assert!(!ed25519ph_verify(&pubkey[..31], &prehashed, &sig, b""));
+    assert!(!ed25519ph_verify(&pubkey, &prehashed[..32], &sig, b""));
+    assert!(!ed25519ph_verify(&pubkey, &prehashed, &sig[..63], b""));
+    assert!(!ed25519ph_verify(&pubkey, &prehashed, &sig, &[0u8; 256]));
+    let mut high_s = sig.clone();
+    high_s[63] |= 0xf0;
+    assert!(!ed25519ph_verify(&pubkey, &prehashed, &high_s, b""));
+}
@@ -13,6 +13,13 @@ use move_vm_runtime::native_functions::NativeContext;
 use smallvec::smallvec;
 use std::{collections::VecDeque, convert::TryFrom};
 
+/// Rust implementation of Move's `native public fun recover(signature: vector<u8>,
+/// message: vector<u8>): (vector<u8>, bool)`
+/// `message` is the raw message a wallet signed with `personal_sign`/`eth_sign`: the prefix
+/// `"\x19Ethereum Signed Message:\n" || len(message)` is applied and keccak256 hashed before
+/// recovery, so callers must not add it themselves.
+/// The flag is false when the signature is malformed or no key can be recovered, in which
+/// case the address is 20 zero bytes and must not be used.
 pub fn native_recover(
     context: &mut NativeContext,
     _ty_args: Vec<Type>,
@@ -28,35 +35,167 @@ pub fn native_recover(
         context.cost_table(),
         NativeCostIndex::ETH_SIGNATURE_RECOVER,
         msg_bytes.len(),
-    );
+    )?;
 
-    let sig = match ethers::core::types::Signature::try_from(sig_bytes.as_slice()) {
-        Ok(sig) => sig,
-        Err(_) => {
-            return Ok(NativeResult::ok(
-                cost,
-                smallvec![Value::vector_u8(vec![0u8; 20])],
-            ));
-        }
+    let (address, success) = match recover(&sig_bytes, &msg_bytes) {
+        Some(address) => (address, true),
+        None => (vec![0u8; 20], false),
     };
 
-    let pubkey = match sig.recover(msg_bytes.as_slice()) {
-        Ok(pubkey) => pubkey,
-        Err(_) => {
-            return Ok(NativeResult::ok(
-                cost,
-                smallvec![Value::vector_u8(vec![0u8; 20])],
-            ));
-        }
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(address), Value::bool(success)],
+    ))
+}
+
+fn recover(sig_bytes: &[u8], msg_bytes: &[u8]) -> Option<Vec<u8>> {
+    let sig = ethers::core::types::Signature::try_from(sig_bytes).ok()?;
+    let pubkey = sig.recover(msg_bytes).ok()?;
+    Some(pubkey.as_bytes().to_vec())
+}
+
+/// Rust implementation of Move's `native public fun recover_pubkey(signature: vector<u8>,
+/// message: vector<u8>): vector<u8>`
+/// Like `recover`, but returns the signer's 64 byte uncompressed secp256k1 public key `X || Y`
+/// instead of its address. 64 zero bytes are returned on failure.
+pub fn native_recover_pubkey(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let msg_bytes = pop_arg!(arguments, Vec<u8>);
+    let sig_bytes = pop_arg!(arguments, Vec<u8>);
+
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::ETH_SIGNATURE_RECOVER,
+        msg_bytes.len(),
+    )?;
+
+    let pubkey = recover_pubkey(&sig_bytes, &msg_bytes).unwrap_or_else(|| vec![0u8; 64]);
+    Ok(NativeResult::ok(cost, smallvec![Value::vector_u8(pubkey)]))
+}
+
+fn recover_pubkey(sig_bytes: &[u8], msg_bytes: &[u8]) -> Option<Vec<u8>> {
+    use ethers::core::secp256k1::{self, Message, RecoveryId, Signature};
+
+    let eth_sig = ethers::core::types::Signature::try_from(sig_bytes).ok()?;
+    // same `v` normalization as `Signature::recover`, which keeps it private
+    let recovery_id = match eth_sig.v {
+        0 | 27 => 0,
+        1 | 28 => 1,
+        v if v >= 35 => ((v - 1) % 2) as u8,
+        _ => return None,
     };
+    let mut rs = [0u8; 64];
+    rs[..32].copy_from_slice(eth_sig.r.as_bytes());
+    rs[32..].copy_from_slice(eth_sig.s.as_bytes());
+
+    let digest = ethers::core::utils::hash_message(msg_bytes);
+    let key = secp256k1::recover(
+        &Message::parse_slice(digest.as_bytes()).ok()?,
+        &Signature::parse(&rs),
+        &RecoveryId::parse(recovery_id).ok()?,
+    )
+    .ok()?;
+    // drop the 0x04 tag of the SEC1 uncompressed encoding
+    Some(key.serialize()[1..].to_vec())
+}
 
+/// Recovers the signer of an EIP-712 typed-data signature.
+/// The signed digest is `keccak256(0x19 || 0x01 || domain_separator || struct_hash)`;
+/// the zero address is returned on any failure.
+pub fn native_recover_typed_data(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
+
+    let struct_hash = pop_arg!(arguments, Vec<u8>);
+    let domain_separator = pop_arg!(arguments, Vec<u8>);
+    let sig_bytes = pop_arg!(arguments, Vec<u8>);
+
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::ETH_SIGNATURE_RECOVER,
+        domain_separator.len() + struct_hash.len(),
+    )?;
+
+    let address = recover_typed_data(&sig_bytes, &domain_separator, &struct_hash)
+        .unwrap_or_else(|| vec![0u8; 20]);
+    Ok(NativeResult::ok(cost, smallvec![Value::vector_u8(address)]))
+}
+
+fn eip712_digest(domain_separator: &[u8], struct_hash: &[u8]) -> Option<[u8; 32]> {
+    if domain_separator.len() != 32 || struct_hash.len() != 32 {
+        return None;
+    }
+    let mut encoded = Vec::with_capacity(66);
+    encoded.extend_from_slice(&[0x19, 0x01]);
+    encoded.extend_from_slice(domain_separator);
+    encoded.extend_from_slice(struct_hash);
+    Some(ethers::core::utils::keccak256(&encoded))
+}
+
+fn recover_typed_data(
+    sig_bytes: &[u8],
+    domain_separator: &[u8],
+    struct_hash: &[u8],
+) -> Option<Vec<u8>> {
+    let digest = eip712_digest(domain_separator, struct_hash)?;
+    let sig = ethers::core::types::Signature::try_from(sig_bytes).ok()?;
+    let address = sig.recover(ethers::core::types::H256::from(digest)).ok()?;
+    Some(address.as_bytes().to_vec())
+}
+
+/// Rust implementation of Move's `native public fun verify(signature: vector<u8>,
+/// pubkey: vector<u8>, message: vector<u8>): bool`
+/// `pubkey` is the signer's 20 byte address. As in `recover`, `message` is hashed with the
+/// `personal_sign` prefix before checking the signature.
+pub fn native_verify(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
+
+    let msg_bytes = pop_arg!(arguments, Vec<u8>);
+    let pubkey_bytes = pop_arg!(arguments, Vec<u8>);
+    let sig_bytes = pop_arg!(arguments, Vec<u8>);
+
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::ETH_SIGNATURE_VERIFY,
+        msg_bytes.len(),
+    )?;
+
+    let verify_result = verify(&sig_bytes, &pubkey_bytes, &msg_bytes);
     Ok(NativeResult::ok(
         cost,
-        smallvec![Value::vector_u8(pubkey.as_bytes().to_vec())],
+        smallvec![Value::bool(verify_result)],
     ))
 }
 
-pub fn native_verify(
+/// Half of the secp256k1 group order n, i.e. floor(n / 2) =
+/// 0x7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a0, big-endian.
+/// For every signature `(r, s)` the pair `(r, n - s)` with the flipped recovery id is valid
+/// too, so only the variant with `s <= SECP256K1_HALF_ORDER` is accepted in strict mode (EIP-2).
+const SECP256K1_HALF_ORDER: [u8; 32] = [
+    0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
+
+/// Rust implementation of Move's `native public fun verify_strict(signature: vector<u8>,
+/// pubkey: vector<u8>, message: vector<u8>): bool`
+/// Like `verify`, but also returns false for malleable signatures whose `s` is above
+/// `SECP256K1_HALF_ORDER`.
+pub fn native_verify_strict(
     context: &mut NativeContext,
     _ty_args: Vec<Type>,
     mut arguments: VecDeque<Value>,
@@ -72,24 +211,231 @@ pub fn native_verify(
         context.cost_table(),
         NativeCostIndex::ETH_SIGNATURE_VERIFY,
         msg_bytes.len(),
-    );
+    )?;
 
-    if pubkey_bytes.len() != 20 {
-        return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)]));
+    let verify_result = has_low_s(&sig_bytes) && verify(&sig_bytes, &pubkey_bytes, &msg_bytes);
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::bool(verify_result)],
+    ))
+}
+
+// Signatures are `r || s || v`; both `s` and the half order are 32 bytes big-endian, so they
+// compare lexicographically.
+fn has_low_s(sig_bytes: &[u8]) -> bool {
+    sig_bytes.len() == 65 && sig_bytes[32..64] <= SECP256K1_HALF_ORDER[..]
+}
+
+/// Abort code of `verify_batch` when the three vectors have different lengths.
+const EVERIFY_BATCH_LENGTH_MISMATCH: u64 = 1;
+
+/// Rust implementation of Move's `native public fun verify_batch(signatures: vector<vector<u8>>,
+/// pubkeys: vector<vector<u8>>, messages: vector<vector<u8>>): vector<bool>`
+/// Checks each entry like `verify` and returns the per-entry results.
+pub fn native_verify_batch(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
+
+    let messages = pop_vec_of_bytes(&mut arguments)?;
+    let pubkeys = pop_vec_of_bytes(&mut arguments)?;
+    let signatures = pop_vec_of_bytes(&mut arguments)?;
+
+    // each entry costs what a single `verify` of its message would
+    let units: usize = messages.iter().map(|msg| std::cmp::max(1, msg.len())).sum();
+    let cost = native_gas(context.cost_table(), NativeCostIndex::ETH_SIGNATURE_VERIFY, units)?;
+
+    if messages.len() != pubkeys.len() || messages.len() != signatures.len() {
+        return Ok(NativeResult::err(cost, EVERIFY_BATCH_LENGTH_MISMATCH));
     }
 
-    let sig = match ethers::core::types::Signature::try_from(sig_bytes.as_slice()) {
+    let results: Vec<bool> = signatures
+        .iter()
+        .zip(&pubkeys)
+        .zip(&messages)
+        .map(|((sig, pubkey), msg)| verify(sig, pubkey, msg))
+        .collect();
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_bool(results)],
+    ))
+}
+
+pub(crate) fn pop_vec_of_bytes(arguments: &mut VecDeque<Value>) -> PartialVMResult<Vec<Vec<u8>>> {
+    pop_arg!(arguments, Vec<Value>)
+        .into_iter()
+        .map(|bytes| bytes.value_as::<Vec<u8>>())
+        .collect()
+}
+
+fn verify(sig_bytes: &[u8], pubkey_bytes: &[u8], msg_bytes: &[u8]) -> bool {
+    if pubkey_bytes.len() != 20 {
+        return false;
+    }
+    let sig = match ethers::core::types::Signature::try_from(sig_bytes) {
         Ok(sig) => sig,
-        Err(_) => {
-            return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)]));
-        }
+        Err(_) => return false,
     };
+    let pubkey = ethers::core::types::H160::from_slice(pubkey_bytes);
+    sig.verify(msg_bytes, pubkey).is_ok()
+}
 
-    let pubkey = ethers::core::types::H160::from_slice(pubkey_bytes.as_slice());
+#[test]
+fn test_recover_typed_data() {
+    // The `Mail` example from the EIP-712 specification, signed by keccak256("cow").
+    let domain_separator =
+        hex::decode("f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f").unwrap();
+    let struct_hash =
+        hex::decode("c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e").unwrap();
+    let sig = hex::decode(concat!(
+        "4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d",
+        "07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562",
+        "1c",
+    ))
+    .unwrap();
+    let signer = hex::decode("cd2a3d9f938e13cd947ec05abc7fe734df8dd826").unwrap();
 
-    let verify_result = sig.verify(msg_bytes.as_slice(), pubkey).is_ok();
-    Ok(NativeResult::ok(
-        cost,
-        smallvec![Value::bool(verify_result)],
+    assert_eq!(
+        eip712_digest(&domain_separator, &struct_hash).unwrap().to_vec(),
+        hex::decode("be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2").unwrap(),
+    );
+    assert_eq!(recover_typed_data(&sig, &domain_separator, &struct_hash), Some(signer.clone()));
+
+    // A different struct hash recovers some other key, and malformed input recovers nothing.
+    let mut tampered = struct_hash.clone();
+    tampered[0] ^= 1;
+    assert_ne!(recover_typed_data(&sig, &domain_separator, &tampered), Some(signer));
+    assert_eq!(recover_typed_data(&sig, &domain_separator, &struct_hash[..31]), None);
+    assert_eq!(recover_typed_data(&sig[..64], &domain_separator, &struct_hash), None);
+}
+
+#[test]
+fn test_verify_batch_entries() {
+    // web3.js `sign("Some data")` with the documentation's example key
+    let msg = b"Some data".to_vec();
+    let sig = hex::decode(concat!(
+        "b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd",
+        "6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a029",
+        "1c",
+    ))
+    .unwrap();
+    let signer = hex::decode("2c7536e3605d9c16a7a3d7b1898e529396a65c23").unwrap();
+
+    let batch = [
+        (sig.clone(), signer.clone(), msg.clone()),
+        (sig.clone(), signer.clone(), b"Other data".to_vec()),
+        (sig.clone(), vec![0u8; 20], msg.clone()),
+        (sig[..64].to_vec(), signer.clone(), msg.clone()),
+        (sig, signer[..19].to_vec(), msg),
+    ];
+    let results: Vec<bool> = batch
+        .iter()
+        .map(|(sig, pubkey, msg)| verify(sig, pubkey, msg))
+        .collect();
+    assert_eq!(results, vec![true, false, false, false, false]);
+}
+
+#[test]
+fn test_recover() {
+    // web3.js `sign("Some data")` with the documentation's example key
+    let sig = hex::decode(concat!(
+        "b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd",
+        "6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a029",
+        "1c",
+    ))
+    .unwrap();
+    let signer = hex::decode("2c7536e3605d9c16a7a3d7b1898e529396a65c23").unwrap();
+
+    assert_eq!(recover(&sig, b"Some data"), Some(signer));
+    // malformed signatures recover nothing
+    assert_eq!(recover(&sig[..64], b"Some data"), None);
+    assert_eq!(recover(&[], b"Some data"), None);
+    let mut bad_v = sig.clone();
+    bad_v[64] = 0x05;
+    assert_eq!(recover(&bad_v, b"Some data"), None);
+}
+
+#[test]
+fn test_personal_sign_prefix() {
+    use ethers::core::{types::{Signature, H256}, utils::{hash_message, keccak256}};
+
+    // web3.js `sign("Some data")`, which is what `personal_sign` produces
+    let sig = hex::decode(concat!(
+        "b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd",
+        "6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a029",
+        "1c",
     ))
-}
\ No newline at end of file
+    .unwrap();
+    let signer = recover(&sig, b"Some data").unwrap();
+    assert_eq!(signer, hex::decode("2c7536e3605d9c16a7a3d7b1898e529396a65c23").unwrap());
+
+    // the signed digest is the prefixed hash ...
+    let prefixed = keccak256(b"\x19Ethereum Signed Message:\n9Some data");
+    assert_eq!(hash_message(b"Some data"), H256::from(prefixed));
+    let sig = Signature::try_from(sig.as_slice()).unwrap();
+    assert_eq!(sig.recover(H256::from(prefixed)).unwrap().as_bytes(), signer.as_slice());
+    // ... not the bare hash of the message
+    let bare = H256::from(keccak256(b"Some data"));
+    assert_ne!(sig.recover(bare).unwrap().as_bytes(), signer.as_slice());
+}
+
+#[test]
+fn test_verify_strict_rejects_high_s() {
+    use ethers::core::types::U256;
+
+    // web3.js `sign("Some data")`, a low-s signature
+    let sig = hex::decode(concat!(
+        "b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd",
+        "6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a029",
+        "1c",
+    ))
+    .unwrap();
+    let signer = hex::decode("2c7536e3605d9c16a7a3d7b1898e529396a65c23").unwrap();
+
+    // its malleable twin: s' = n - s with the recovery id flipped
+    let n = U256::from_big_endian(
+        &hex::decode("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141").unwrap(),
+    );
+    let mut high_s = sig.clone();
+    (n - U256::from_big_endian(&sig[32..64])).to_big_endian(&mut high_s[32..64]);
+    high_s[64] = 0x1b;
+
+    assert_eq!(n / 2, U256::from_big_endian(&SECP256K1_HALF_ORDER));
+    assert!(has_low_s(&sig));
+    assert!(!has_low_s(&high_s));
+
+    // both recover the same signer, so only the strict check tells them apart
+    assert_eq!(recover(&high_s, b"Some data"), Some(signer.clone()));
+    assert!(verify(&high_s, &signer, b"Some data"));
+    assert!(has_low_s(&sig) && verify(&sig, &signer, b"Some data"));
+}
+
+#[test]
+fn test_recover_pubkey() {
+    use ethers::core::utils::keccak256;
+
+    // web3.js `sign("Some data")` with the documentation's example key
+    let sig = hex::decode(concat!(
+        "b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd",
+        "6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a029",
+        "1c",
+    ))
+    .unwrap();
+
+    let pubkey = recover_pubkey(&sig, b"Some data").unwrap();
+    assert_eq!(pubkey.len(), 64);
+    // the address is the last 20 bytes of the key's hash
+    assert_eq!(keccak256(&pubkey)[12..].to_vec(), recover(&sig, b"Some data").unwrap());
+
+    // a different message recovers a different key
+    assert_ne!(recover_pubkey(&sig, b"Other data"), Some(pubkey));
+    // malformed signatures recover nothing
+    assert_eq!(recover_pubkey(&sig[..64], b"Some data"), None);
+    let mut bad_v = sig.clone();
+    bad_v[64] = 0x05;
+    assert_eq!(recover_pubkey(&bad_v, b"Some data"), None);
+}
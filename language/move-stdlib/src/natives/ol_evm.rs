@@ -0,0 +1,389 @@
+//////// 0L ////////
+// SPDX-License-Identifier: Apache-2.0
+use crate::natives::{ol_eth_signature::pop_vec_of_bytes, ol_hash::keccak_256};
+use move_binary_format::errors::PartialVMResult;
+use move_vm_runtime::native_functions::NativeContext;
+use move_vm_types::{
+    gas_schedule::NativeCostIndex,
+    loaded_data::runtime_types::Type,
+    natives::function::{native_gas, NativeResult},
+    pop_arg,
+    values::Value,
+};
+use smallvec::smallvec;
+use std::{cmp::Ordering, collections::VecDeque};
+
+/// Rust implementation of Move's `native public fun semver_compare(a: vector<u8>,
+/// b: vector<u8>): (bool, u8)`
+/// Orders two SemVer 2.0.0 strings by precedence: 0 if `a` is lower, 1 if they have the same
+/// precedence and 2 if `a` is higher. Build metadata is ignored. The flag is false, with an
+/// ordering of 0, when either string is not a valid version.
+pub fn native_semver_compare(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let b = pop_arg!(arguments, Vec<u8>);
+    let a = pop_arg!(arguments, Vec<u8>);
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::DECIMAL, a.len() + b.len())?;
+
+    let (ok, ordering) = match semver_compare(&a, &b) {
+        Some(Ordering::Less) => (true, 0),
+        Some(Ordering::Equal) => (true, 1),
+        Some(Ordering::Greater) => (true, 2),
+        None => (false, 0),
+    };
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::bool(ok), Value::u8(ordering)],
+    ))
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Identifier<'a> {
+    // numeric identifiers sort below alphanumeric ones
+    Numeric(u64),
+    Alphanumeric(&'a str),
+}
+
+#[derive(Debug)]
+struct Version<'a> {
+    core: [u64; 3],
+    pre_release: Vec<Identifier<'a>>,
+}
+
+impl<'a> Version<'a> {
+    fn parse(bytes: &'a [u8]) -> Option<Self> {
+        let s = std::str::from_utf8(bytes).ok()?;
+        let (s, build) = match s.find('+') {
+            Some(i) => (&s[..i], Some(&s[i + 1..])),
+            None => (s, None),
+        };
+        if let Some(build) = build {
+            if !build.split('.').all(is_identifier) {
+                return None;
+            }
+        }
+        let (core, pre_release) = match s.find('-') {
+            Some(i) => (&s[..i], Some(&s[i + 1..])),
+            None => (s, None),
+        };
+
+        let mut parts = core.split('.');
+        let mut version = Version { core: [0; 3], pre_release: vec![] };
+        for number in version.core.iter_mut() {
+            *number = parse_numeric(parts.next()?)?;
+        }
+        if parts.next().is_some() {
+            return None;
+        }
+        if let Some(pre_release) = pre_release {
+            for identifier in pre_release.split('.') {
+                if !is_identifier(identifier) {
+                    return None;
+                }
+                version.pre_release.push(if identifier.bytes().all(|b| b.is_ascii_digit()) {
+                    Identifier::Numeric(parse_numeric(identifier)?)
+                } else {
+                    Identifier::Alphanumeric(identifier)
+                });
+            }
+        }
+        Some(version)
+    }
+
+    fn precedence(&self, other: &Self) -> Ordering {
+        self.core.cmp(&other.core).then_with(|| {
+            // a pre-release sorts below the release itself
+            match (self.pre_release.is_empty(), other.pre_release.is_empty()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => self.pre_release.cmp(&other.pre_release),
+            }
+        })
+    }
+}
+
+fn is_identifier(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+}
+
+// Numbers are plain decimal digits without leading zeros.
+fn parse_numeric(s: &str) -> Option<u64> {
+    let digits = !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    if !digits || (s.len() > 1 && s.starts_with('0')) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+fn semver_compare(a: &[u8], b: &[u8]) -> Option<Ordering> {
+    Some(Version::parse(a)?.precedence(&Version::parse(b)?))
+}
+
+/// Rust implementation of Move's `native public fun log_matches_filter(
+/// log_topics: vector<vector<u8>>, filter: vector<vector<u8>>): bool`
+/// Applies `eth_getLogs` topic filtering: `filter[i]` must equal `log_topics[i]`, and an empty
+/// entry is a wildcard for that position. A filter with more entries than the log has topics
+/// never matches, even if the extra entries are wildcards.
+pub fn native_log_matches_filter(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let filter = pop_vec_of_bytes(&mut arguments)?;
+    let log_topics = pop_vec_of_bytes(&mut arguments)?;
+
+    let compared_bytes: usize = filter.iter().map(|topic| topic.len()).sum();
+    let cost = native_gas(context.cost_table(), NativeCostIndex::DECIMAL, compared_bytes)?;
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::bool(log_matches_filter(&log_topics, &filter))],
+    ))
+}
+
+fn log_matches_filter(log_topics: &[Vec<u8>], filter: &[Vec<u8>]) -> bool {
+    filter.len() <= log_topics.len()
+        && filter
+            .iter()
+            .zip(log_topics)
+            .all(|(wanted, topic)| wanted.is_empty() || wanted == topic)
+}
+
+/// Abort code of `abi_encode_hash` when a field is longer than a 32 byte word.
+const EFIELD_TOO_LONG: u64 = 1;
+
+/// Rust implementation of Move's `native public fun abi_encode_hash(
+/// word_fields: vector<vector<u8>>): vector<u8>`
+/// Computes `keccak256(abi.encode(...))` for static fields given as big-endian bytes: each
+/// field is left-padded with zeros to 32 bytes, as for `uintN`, `address` and `bytes32` alike.
+pub fn native_abi_encode_hash(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let word_fields = pop_vec_of_bytes(&mut arguments)?;
+
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::KECCAK_256,
+        32 * word_fields.len(),
+    )?;
+
+    match abi_encode_hash(&word_fields) {
+        Some(hash) => Ok(NativeResult::ok(cost, smallvec![Value::vector_u8(hash.to_vec())])),
+        None => Ok(NativeResult::err(cost, EFIELD_TOO_LONG)),
+    }
+}
+
+fn abi_encode_hash(word_fields: &[Vec<u8>]) -> Option<[u8; 32]> {
+    let mut encoded = Vec::with_capacity(32 * word_fields.len());
+    for field in word_fields {
+        let padding = 32usize.checked_sub(field.len())?;
+        encoded.extend_from_slice(&[0u8; 32][..padding]);
+        encoded.extend_from_slice(field);
+    }
+    Some(keccak_256(&encoded))
+}
+
+/// Abort code of `ens_namehash` when the name is not UTF-8 or fails UTS-46 normalization.
+const EINVALID_NAME: u64 = 2;
+
+/// Rust implementation of Move's `native public fun ens_namehash(name: vector<u8>): vector<u8>`
+/// Normalizes `name` with UTS-46 (non-transitional, STD3 rules) as in ENSIP-1, then folds its
+/// labels from the top level down: `node = keccak256(node || keccak256(label))`, starting from
+/// 32 zero bytes. The empty name is the zero node. Aborts with `EINVALID_NAME` if the name is
+/// rejected by the normalization or has an empty label, e.g. `foo..eth` or `eth.`.
+pub fn native_ens_namehash(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let name = pop_arg!(arguments, Vec<u8>);
+
+    let normalized = normalize_ens_name(&name);
+    // the normalization reads the whole name, then the labels are hashed
+    let hashed_bytes = normalized.as_deref().map_or(0, namehash_hashed_bytes);
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::KECCAK_256,
+        name.len() + hashed_bytes,
+    )?;
+
+    match normalized.and_then(|normalized| ens_namehash(&normalized)) {
+        Some(node) => Ok(NativeResult::ok(cost, smallvec![Value::vector_u8(node.to_vec())])),
+        None => Ok(NativeResult::err(cost, EINVALID_NAME)),
+    }
+}
+
+// `None` if `name` is not UTF-8 or is rejected by UTS-46.
+fn normalize_ens_name(name: &[u8]) -> Option<String> {
+    let name = std::str::from_utf8(name).ok()?;
+    if name.is_empty() {
+        return Some(String::new());
+    }
+    let (normalized, result) = idna::Config::default()
+        .use_std3_ascii_rules(true)
+        .transitional_processing(false)
+        .to_unicode(name);
+    result.ok()?;
+    Some(normalized)
+}
+
+// Bytes hashed by `ens_namehash`: every label, with the 32 byte hash it produces, and the
+// 64 byte parent node and label hash pair for every level.
+fn namehash_hashed_bytes(normalized: &str) -> usize {
+    if normalized.is_empty() {
+        return 0;
+    }
+    normalized.split('.').map(|label| label.len() + 32 + 64).sum()
+}
+
+// Hashes a normalized name. `None` if it has an empty label.
+fn ens_namehash(normalized: &str) -> Option<[u8; 32]> {
+    let mut node = [0u8; 32];
+    if normalized.is_empty() {
+        return Some(node);
+    }
+    if normalized.split('.').any(str::is_empty) {
+        return None;
+    }
+
+    for label in normalized.rsplit('.') {
+        let label_hash = keccak_256(label.as_bytes());
+        node = keccak_256(&[node, label_hash].concat());
+    }
+    Some(node)
+}
+
+#[test]
+fn test_semver_compare() {
+    let cmp = |a: &str, b: &str| semver_compare(a.as_bytes(), b.as_bytes());
+
+    assert_eq!(cmp("1.2.0", "1.10.0"), Some(Ordering::Less));
+    assert_eq!(cmp("2.0.0", "1.99.99"), Some(Ordering::Greater));
+    assert_eq!(cmp("1.0.0", "1.0.0"), Some(Ordering::Equal));
+    // build metadata does not affect precedence
+    assert_eq!(cmp("1.0.0+build.5", "1.0.0+other"), Some(Ordering::Equal));
+
+    // pre-releases, in the order given by the specification
+    assert_eq!(cmp("1.0.0-rc", "1.0.0"), Some(Ordering::Less));
+    let ordered = [
+        "1.0.0-alpha",
+        "1.0.0-alpha.1",
+        "1.0.0-alpha.beta",
+        "1.0.0-beta",
+        "1.0.0-beta.2",
+        "1.0.0-beta.11",
+        "1.0.0-rc.1",
+        "1.0.0",
+    ];
+    for pair in ordered.windows(2) {
+        assert_eq!(cmp(pair[0], pair[1]), Some(Ordering::Less), "{} < {}", pair[0], pair[1]);
+    }
+
+    // invalid versions
+    for invalid in ["1.2", "1.2.3.4", "01.2.3", "1.2.3-", "1.2.3-rc..1", "1.2.3-01", "v1.2.3", ""] {
+        assert_eq!(cmp(invalid, "1.0.0"), None, "{}", invalid);
+    }
+    assert_eq!(cmp("1.0.0", "1.0.0+"), None);
+}
+
+#[test]
+fn test_log_matches_filter() {
+    let transfer = vec![0xdd; 32];
+    let (alice, bob) = (vec![0xaa; 32], vec![0xbb; 32]);
+    let log = vec![transfer.clone(), alice.clone(), bob.clone()];
+
+    // exact match, on all topics or a prefix of them
+    assert!(log_matches_filter(&log, &log));
+    assert!(log_matches_filter(&log, &log[..1]));
+    assert!(log_matches_filter(&log, &[]));
+    // wildcards, e.g. any transfer to bob
+    assert!(log_matches_filter(&log, &[transfer.clone(), vec![], bob.clone()]));
+    assert!(log_matches_filter(&log, &[vec![], vec![], vec![]]));
+
+    // a different topic in any position
+    assert!(!log_matches_filter(&log, &[transfer.clone(), bob.clone()]));
+    assert!(!log_matches_filter(&log, &[vec![], vec![], alice]));
+    // a filter longer than the log
+    assert!(!log_matches_filter(&log, &[transfer, vec![], bob, vec![]]));
+}
+
+#[test]
+fn test_abi_encode_hash() {
+    let keccak = keccak_256;
+    let account = hex::decode("2c7536e3605d9c16a7a3d7b1898e529396a65c23").unwrap();
+
+    // abi.encode(uint256(5), account)
+    let encoded = hex::decode(concat!(
+        "0000000000000000000000000000000000000000000000000000000000000005",
+        "0000000000000000000000002c7536e3605d9c16a7a3d7b1898e529396a65c23",
+    ))
+    .unwrap();
+    assert_eq!(abi_encode_hash(&[vec![5], account.clone()]), Some(keccak(&encoded)));
+    // leading zero bytes in a field make no difference
+    assert_eq!(abi_encode_hash(&[vec![0, 0, 5], account.clone()]), Some(keccak(&encoded)));
+    // unlike encodePacked, every field takes a full word
+    let packed = [&[5], &account[..]].concat();
+    assert_ne!(abi_encode_hash(&[vec![5], account.clone()]), Some(keccak(&packed)));
+
+    assert_eq!(abi_encode_hash(&[vec![0xff; 32]]), Some(keccak(&[0xff; 32])));
+    assert_eq!(abi_encode_hash(&[vec![0xff; 33]]), None);
+}
+
+#[test]
+fn test_ens_namehash() {
+    let node = |hash: &str| {
+        let mut node = [0u8; 32];
+        node.copy_from_slice(&hex::decode(hash).unwrap());
+        Some(node)
+    };
+    let ens_namehash = |name: &[u8]| normalize_ens_name(name).and_then(|n| ens_namehash(&n));
+    let eth = node("93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae");
+    let foo_eth = node("de9b09fd7c5f901e23a3f19fecc54828e9c848539801e86591bd9801b019f84f");
+    let alice_eth = node("787192fc5378cc32aa956ddfdedbf26b24e8d78e40109add0eea2c1a012c3dec");
+
+    assert_eq!(ens_namehash(b""), Some([0u8; 32]));
+    assert_eq!(ens_namehash(b"eth"), eth);
+    assert_eq!(ens_namehash(b"foo.eth"), foo_eth);
+    assert_eq!(ens_namehash(b"alice.eth"), alice_eth);
+    // names are normalized before hashing
+    assert_eq!(ens_namehash(b"Alice.ETH"), alice_eth);
+    assert_eq!(ens_namehash("ａｌｉｃｅ.eth".as_bytes()), alice_eth);
+
+    // disallowed characters and invalid UTF-8 abort
+    assert_eq!(ens_namehash(b"alice eth"), None);
+    assert_eq!(ens_namehash(b"alice\xff.eth"), None);
+    // so do empty labels
+    assert_eq!(ens_namehash(b"foo..eth"), None);
+    assert_eq!(ens_namehash(b".eth"), None);
+    assert_eq!(ens_namehash(b"eth."), None);
+    assert_eq!(ens_namehash(b"."), None);
+}
+
+#[test]
+fn test_namehash_hashed_bytes() {
+    assert_eq!(namehash_hashed_bytes(""), 0);
+    assert_eq!(namehash_hashed_bytes("eth"), 3 + 96);
+    assert_eq!(namehash_hashed_bytes("alice.eth"), 5 + 3 + 2 * 96);
+    // short labels are charged for the nodes they hash, not only for their length
+    let many_labels = vec!["a"; 100].join(".");
+    assert_eq!(namehash_hashed_bytes(&many_labels), 100 * 97);
+}
@@ -2,12 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use move_binary_format::errors::PartialVMResult;
+use move_core_types::vm_status::sub_status::NFE_BCS_SERIALIZATION_FAILURE;
 use move_vm_types::{
     gas_schedule::NativeCostIndex,
     loaded_data::runtime_types::Type,
     natives::function::{native_gas, NativeResult},
     pop_arg,    
-    values::Value,
+    values::{Reference, Value},
 };
 use move_vm_runtime::native_functions::NativeContext;
 use smallvec::smallvec;
@@ -28,17 +29,918 @@ pub fn native_keccak_256(
         context.cost_table(),
         NativeCostIndex::KECCAK_256,
         hash_arg.len(),
-    );
+    )?;
 
-    let mut sha3 = ::tiny_keccak::Keccak::v256();
-    let data = hash_arg.as_slice();
-    sha3.update(&data);
-    let mut output = [0u8; 32];
-    sha3.finalize(&mut output);
-    let hash_vec = output.to_vec();
+    let hash_vec = keccak_256(&hash_arg).to_vec();
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(hash_vec)],
+    ))
+}
+
+pub fn native_keccak_512(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let hash_arg = pop_arg!(arguments, Vec<u8>);
+
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::KECCAK_256,
+        hash_arg.len(),
+    )?;
+
+    let hash_vec = keccak_512(&hash_arg).to_vec();
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(hash_vec)],
+    ))
+}
+
+pub fn native_sha3_512(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let hash_arg = pop_arg!(arguments, Vec<u8>);
+
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::KECCAK_256,
+        hash_arg.len(),
+    )?;
+
+    let hash_vec = sha3_512(&hash_arg).to_vec();
 
     Ok(NativeResult::ok(
         cost,
         smallvec![Value::vector_u8(hash_vec)],
     ))
-}
\ No newline at end of file
+}
+
+/// Rust implementation of Move's `native public fun keccak_256_chunks(chunks: vector<vector<u8>>):
+/// vector<u8>`
+/// Same as `keccak_256` of the concatenated chunks, without joining them in Move.
+pub fn native_keccak_256_chunks(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let chunks = pop_arg!(arguments, Vec<Value>)
+        .into_iter()
+        .map(|chunk| chunk.value_as::<Vec<u8>>())
+        .collect::<PartialVMResult<Vec<_>>>()?;
+
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::KECCAK_256,
+        chunks.iter().map(Vec::len).sum(),
+    )?;
+
+    let mut output = [0u8; 32];
+    hash_chunks(::tiny_keccak::Keccak::v256(), &chunks, &mut output);
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(output.to_vec())],
+    ))
+}
+
+/// Rust implementation of Move's `native public fun keccak_512_chunks(chunks: vector<vector<u8>>):
+/// vector<u8>`
+/// Same as `keccak_512` of the concatenated chunks, without joining them in Move.
+pub fn native_keccak_512_chunks(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let chunks = pop_arg!(arguments, Vec<Value>)
+        .into_iter()
+        .map(|chunk| chunk.value_as::<Vec<u8>>())
+        .collect::<PartialVMResult<Vec<_>>>()?;
+
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::KECCAK_256,
+        chunks.iter().map(Vec::len).sum(),
+    )?;
+
+    let mut output = [0u8; 64];
+    hash_chunks(::tiny_keccak::Keccak::v512(), &chunks, &mut output);
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(output.to_vec())],
+    ))
+}
+
+/// Abort code of `blake3` when more than `MAX_BLAKE3_OUTPUT_LEN` bytes are requested.
+const EBLAKE3_OUTPUT_TOO_LONG: u64 = 3;
+
+/// Largest output, in bytes, that `blake3` will produce.
+const MAX_BLAKE3_OUTPUT_LEN: u64 = 1024;
+
+/// Rust implementation of Move's `native public fun blake3(data: vector<u8>, output_len: u64):
+/// vector<u8>`
+/// Reads `output_len` bytes from BLAKE3's extendable output; the first 32 bytes are the
+/// standard digest. Gas is charged for both the input and the output length.
+pub fn native_blake3(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let output_len = pop_arg!(arguments, u64);
+    let hash_arg = pop_arg!(arguments, Vec<u8>);
+
+    if output_len > MAX_BLAKE3_OUTPUT_LEN {
+        let cost = native_gas(context.cost_table(), NativeCostIndex::KECCAK_256, hash_arg.len())?;
+        return Ok(NativeResult::err(cost, EBLAKE3_OUTPUT_TOO_LONG));
+    }
+
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::KECCAK_256,
+        hash_arg.len() + output_len as usize,
+    )?;
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(blake3_xof(&hash_arg, output_len as usize))],
+    ))
+}
+
+/// Rust implementation of Move's `native public fun u128_order_preserving(value: u128): vector<u8>`
+/// The big-endian bytes of an unsigned integer already sort lexicographically
+/// in numeric order.
+pub fn native_u128_order_preserving(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let value = pop_arg!(arguments, u128);
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::DECIMAL, 16)?;
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(u128_order_preserving(value).to_vec())],
+    ))
+}
+
+/// Rust implementation of Move's `native public fun i128_order_preserving(value: u128): vector<u8>`
+/// Move has no signed integers, so `value` carries the two's complement bits of an i128.
+pub fn native_i128_order_preserving(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let value = pop_arg!(arguments, u128);
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::DECIMAL, 16)?;
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(i128_order_preserving(value as i128).to_vec())],
+    ))
+}
+
+fn u128_order_preserving(value: u128) -> [u8; 16] {
+    value.to_be_bytes()
+}
+
+// Flipping the sign bit moves negative values below positive ones while
+// keeping the order within each half.
+fn i128_order_preserving(value: i128) -> [u8; 16] {
+    ((value as u128) ^ (1u128 << 127)).to_be_bytes()
+}
+
+/// Rust implementation of Move's `native public fun unique_id<T>(v: &T, salt: vector<u8>): vector<u8>`
+/// The id is `keccak256(bcs(v) || salt)`.
+pub fn native_unique_id(
+    context: &mut NativeContext,
+    mut ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 1);
+    debug_assert!(arguments.len() == 2);
+
+    let salt = pop_arg!(arguments, Vec<u8>);
+    let ref_to_val = pop_arg!(arguments, Reference);
+
+    let arg_type = ty_args.pop().unwrap();
+    let serialized_value_opt = match context.type_to_type_layout(&arg_type)? {
+        None => None,
+        Some(layout) => ref_to_val.read_ref()?.simple_serialize(&layout),
+    };
+    let serialized_value = match serialized_value_opt {
+        None => {
+            let cost = native_gas(context.cost_table(), NativeCostIndex::KECCAK_256, salt.len())?;
+            return Ok(NativeResult::err(cost, NFE_BCS_SERIALIZATION_FAILURE));
+        }
+        Some(serialized_value) => serialized_value,
+    };
+
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::KECCAK_256,
+        serialized_value.len() + salt.len(),
+    )?;
+
+    let id = unique_id(&serialized_value, &salt);
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(id.to_vec())],
+    ))
+}
+
+/// Abort code of `verify_merkle_batch` when `leaves` and `proofs` have different lengths.
+const EMERKLE_LENGTH_MISMATCH: u64 = 1;
+
+/// Rust implementation of Move's `native public fun verify_merkle_batch(
+/// leaves: vector<vector<u8>>, proofs: vector<vector<vector<u8>>>, root: vector<u8>): vector<bool>`
+/// Leaves are leaf hashes. Each proof lists the sibling hashes from the leaf up to the root,
+/// and every pair is hashed in sorted order with keccak256.
+pub fn native_verify_merkle_batch(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
+
+    let root = pop_arg!(arguments, Vec<u8>);
+    let proofs = pop_arg!(arguments, Vec<Value>)
+        .into_iter()
+        .map(|proof| {
+            proof
+                .value_as::<Vec<Value>>()?
+                .into_iter()
+                .map(|sibling| sibling.value_as::<Vec<u8>>())
+                .collect::<PartialVMResult<Vec<_>>>()
+        })
+        .collect::<PartialVMResult<Vec<_>>>()?;
+    let leaves = pop_arg!(arguments, Vec<Value>)
+        .into_iter()
+        .map(|leaf| leaf.value_as::<Vec<u8>>())
+        .collect::<PartialVMResult<Vec<_>>>()?;
+
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::KECCAK_256,
+        merkle_batch_hashed_bytes(&leaves, &proofs),
+    )?;
+
+    if leaves.len() != proofs.len() {
+        return Ok(NativeResult::err(cost, EMERKLE_LENGTH_MISMATCH));
+    }
+
+    let results: Vec<bool> = leaves
+        .iter()
+        .zip(&proofs)
+        .map(|(leaf, proof)| verify_merkle_proof(leaf, proof, &root))
+        .collect();
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_bool(results)],
+    ))
+}
+
+/// Abort code of the storage slot natives when a slot is not 32 bytes.
+const EINVALID_SLOT: u64 = 2;
+
+/// Rust implementation of Move's `native public fun nested_mapping_slot(
+/// keys: vector<vector<u8>>, base_slot: vector<u8>): vector<u8>`
+/// Follows Solidity's storage layout, where the slot of `m[k]` is `keccak256(k || slot(m))`,
+/// folding once per key from the outermost mapping inward. Keys are hashed as given, so
+/// value-type keys (addresses, integers) must already be left-padded to 32 bytes.
+pub fn native_nested_mapping_slot(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let base_slot = pop_arg!(arguments, Vec<u8>);
+    let keys = pop_arg!(arguments, Vec<Value>)
+        .into_iter()
+        .map(|key| key.value_as::<Vec<u8>>())
+        .collect::<PartialVMResult<Vec<_>>>()?;
+
+    // every step hashes the key followed by a 32 byte slot
+    let hashed_bytes: usize = keys.iter().map(|key| key.len() + 32).sum();
+    let cost = native_gas(context.cost_table(), NativeCostIndex::KECCAK_256, hashed_bytes)?;
+
+    if base_slot.len() != 32 {
+        return Ok(NativeResult::err(cost, EINVALID_SLOT));
+    }
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(nested_mapping_slot(&keys, &base_slot))],
+    ))
+}
+
+fn nested_mapping_slot(keys: &[Vec<u8>], base_slot: &[u8]) -> Vec<u8> {
+    keys.iter().fold(base_slot.to_vec(), |slot, key| {
+        keccak_256(&[key.as_slice(), &slot].concat()).to_vec()
+    })
+}
+
+/// Rust implementation of Move's `native public fun array_element_slot(
+/// slot: vector<u8>, index: u64): vector<u8>`
+/// Follows Solidity's storage layout, where the elements of a dynamic array declared at `slot`
+/// start at `keccak256(slot)`. Every element is assumed to take a full slot, and the addition
+/// wraps around like EVM arithmetic.
+pub fn native_array_element_slot(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let index = pop_arg!(arguments, u64);
+    let slot = pop_arg!(arguments, Vec<u8>);
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::KECCAK_256, 32)?;
+
+    if slot.len() != 32 {
+        return Ok(NativeResult::err(cost, EINVALID_SLOT));
+    }
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(array_element_slot(&slot, index).to_vec())],
+    ))
+}
+
+fn array_element_slot(slot: &[u8], index: u64) -> [u8; 32] {
+    let mut element = keccak_256(slot);
+    // big-endian addition of the index into the last bytes, dropping the final carry
+    let mut carry = index as u128;
+    for byte in element.iter_mut().rev() {
+        if carry == 0 {
+            break;
+        }
+        let sum = *byte as u128 + (carry & 0xff);
+        *byte = sum as u8;
+        carry = (carry >> 8) + (sum >> 8);
+    }
+    element
+}
+
+/// Abort code of `airdrop_leaf` when `account` is not a 20 byte address.
+const EINVALID_ADDRESS: u64 = 4;
+
+/// Rust implementation of Move's `native public fun airdrop_leaf(index: u128, account: vector<u8>,
+/// amount: u128): vector<u8>`
+/// Computes `keccak256(abi.encodePacked(uint256 index, address account, uint256 amount))`, the
+/// leaf of Uniswap style merkle distributors. The packed layout is 84 bytes: `index` as a
+/// 32 byte big-endian word, the 20 address bytes without padding, then `amount` as a 32 byte
+/// big-endian word.
+pub fn native_airdrop_leaf(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
+
+    let amount = pop_arg!(arguments, u128);
+    let account = pop_arg!(arguments, Vec<u8>);
+    let index = pop_arg!(arguments, u128);
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::KECCAK_256, 84)?;
+
+    if account.len() != 20 {
+        return Ok(NativeResult::err(cost, EINVALID_ADDRESS));
+    }
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(airdrop_leaf(index, &account, amount).to_vec())],
+    ))
+}
+
+fn airdrop_leaf(index: u128, account: &[u8], amount: u128) -> [u8; 32] {
+    let mut packed = Vec::with_capacity(84);
+    packed.extend_from_slice(&[0u8; 16]);
+    packed.extend_from_slice(&index.to_be_bytes());
+    packed.extend_from_slice(account);
+    packed.extend_from_slice(&[0u8; 16]);
+    packed.extend_from_slice(&amount.to_be_bytes());
+    keccak_256(&packed)
+}
+
+/// Rust implementation of Move's `native public fun chunk_id(data: vector<u8>): vector<u8>`
+/// Computes `keccak256(len(data) || data)` with the length as 8 big-endian bytes, so the id
+/// commits to where a chunk ends and differently framed chunks of the same content never
+/// collide.
+pub fn native_chunk_id(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let data = pop_arg!(arguments, Vec<u8>);
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::KECCAK_256, 8 + data.len())?;
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(chunk_id(&data).to_vec())],
+    ))
+}
+
+fn chunk_id(data: &[u8]) -> [u8; 32] {
+    keccak_256(&[&(data.len() as u64).to_be_bytes()[..], data].concat())
+}
+
+/// Rust implementation of Move's `native public fun multiset_hash(items: vector<vector<u8>>):
+/// vector<u8>`
+/// Sorts `items`, keeping duplicates, and folds them as `root = keccak256(root || keccak256(item))`
+/// from a zero root. The hash depends on which items occur and how often, not on their order.
+pub fn native_multiset_hash(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let mut items = pop_arg!(arguments, Vec<Value>)
+        .into_iter()
+        .map(|item| item.value_as::<Vec<u8>>())
+        .collect::<PartialVMResult<Vec<_>>>()?;
+
+    // every item is hashed on its own and then with the 32 byte root
+    let hashed_bytes: usize = items.iter().map(|item| item.len() + 64).sum();
+    let cost = native_gas(context.cost_table(), NativeCostIndex::KECCAK_256, hashed_bytes)?;
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(multiset_hash(&mut items).to_vec())],
+    ))
+}
+
+fn multiset_hash(items: &mut [Vec<u8>]) -> [u8; 32] {
+    items.sort();
+    items.iter().fold([0u8; 32], |root, item| {
+        keccak_256(&[&root[..], &keccak_256(item)[..]].concat())
+    })
+}
+
+/// Abort code of `verify_hashchain` when more than `MAX_HASHCHAIN_ITERATIONS` are requested.
+const EHASHCHAIN_TOO_LONG: u64 = 5;
+
+/// Longest chain `verify_hashchain` will walk. The work happens before gas is charged, so it
+/// has to be bounded up front.
+const MAX_HASHCHAIN_ITERATIONS: u64 = 100_000;
+
+/// Rust implementation of Move's `native public fun verify_hashchain(start: vector<u8>,
+/// iterations: u64, target: vector<u8>): bool`
+/// Whether hashing `start` with keccak256 `iterations` times gives `target`. Zero iterations
+/// compare `start` itself. Gas is charged per iteration.
+pub fn native_verify_hashchain(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
+
+    let target = pop_arg!(arguments, Vec<u8>);
+    let iterations = pop_arg!(arguments, u64);
+    let start = pop_arg!(arguments, Vec<u8>);
+
+    if iterations > MAX_HASHCHAIN_ITERATIONS {
+        let cost = native_gas(context.cost_table(), NativeCostIndex::KECCAK_256, 1)?;
+        return Ok(NativeResult::err(cost, EHASHCHAIN_TOO_LONG));
+    }
+
+    // the first link hashes `start`, every later one a 32 byte hash
+    let hashed_bytes = start.len() + 32 * iterations as usize;
+    let cost = native_gas(context.cost_table(), NativeCostIndex::KECCAK_256, hashed_bytes)?;
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::bool(hashchain(&start, iterations) == target)],
+    ))
+}
+
+fn hashchain(start: &[u8], iterations: u64) -> Vec<u8> {
+    (0..iterations).fold(start.to_vec(), |link, _| keccak_256(&link).to_vec())
+}
+
+/// Abort code of `keccak_packed` when `widths` and `values` have different lengths.
+const EPACKED_LENGTH_MISMATCH: u64 = 6;
+/// Abort code of `keccak_packed` when a width is 0, above 32, or too narrow for its value.
+const EPACKED_INVALID_WIDTH: u64 = 7;
+
+/// Rust implementation of Move's `native public fun keccak_packed(widths: vector<u8>,
+/// values: vector<u128>): vector<u8>`
+/// Computes the keccak256 of `abi.encodePacked` over unsigned integers, where `widths[i]` is
+/// the byte width of `values[i]`: 1 for `uint8`, 8 for `uint64`, 32 for `uint256` and so on.
+/// Each value is written big-endian in exactly its width, zero-extended above 16 bytes.
+pub fn native_keccak_packed(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let values = pop_arg!(arguments, Vec<u128>);
+    let widths = pop_arg!(arguments, Vec<u8>);
+
+    let hashed_bytes: usize = widths.iter().map(|width| *width as usize).sum();
+    let cost = native_gas(context.cost_table(), NativeCostIndex::KECCAK_256, hashed_bytes)?;
+
+    match keccak_packed(&widths, &values) {
+        Ok(hash) => Ok(NativeResult::ok(cost, smallvec![Value::vector_u8(hash.to_vec())])),
+        Err(abort_code) => Ok(NativeResult::err(cost, abort_code)),
+    }
+}
+
+fn keccak_packed(widths: &[u8], values: &[u128]) -> Result<[u8; 32], u64> {
+    if widths.len() != values.len() {
+        return Err(EPACKED_LENGTH_MISMATCH);
+    }
+    let mut packed = Vec::new();
+    for (width, value) in widths.iter().zip(values) {
+        let width = *width as usize;
+        let significant = 16 - value.leading_zeros() as usize / 8;
+        if width == 0 || width > 32 || significant > width {
+            return Err(EPACKED_INVALID_WIDTH);
+        }
+        let mut word = [0u8; 32];
+        word[16..].copy_from_slice(&value.to_be_bytes());
+        packed.extend_from_slice(&word[32 - width..]);
+    }
+    Ok(keccak_256(&packed))
+}
+
+fn verify_merkle_proof(leaf: &[u8], proof: &[Vec<u8>], root: &[u8]) -> bool {
+    let computed = proof.iter().fold(leaf.to_vec(), |node, sibling| {
+        hash_merkle_pair(&node, sibling).to_vec()
+    });
+    computed == root
+}
+
+// Upper bound on the bytes `verify_merkle_proof` hashes for the batch. Nodes are arbitrary
+// length, so each leaf and sibling is charged for its own length, plus the 32 byte hash
+// carried into every level.
+fn merkle_batch_hashed_bytes(leaves: &[Vec<u8>], proofs: &[Vec<Vec<u8>>]) -> usize {
+    let leaf_bytes: usize = leaves.iter().map(|leaf| leaf.len()).sum();
+    let proof_bytes: usize = proofs
+        .iter()
+        .flatten()
+        .map(|sibling| sibling.len() + 32)
+        .sum();
+    leaf_bytes + proof_bytes
+}
+
+fn hash_merkle_pair(a: &[u8], b: &[u8]) -> [u8; 32] {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    keccak_256(&[first, second].concat())
+}
+
+fn unique_id(serialized_value: &[u8], salt: &[u8]) -> [u8; 32] {
+    keccak_256(&[serialized_value, salt].concat())
+}
+
+pub(crate) fn keccak_256(data: &[u8]) -> [u8; 32] {
+    let mut sha3 = ::tiny_keccak::Keccak::v256();
+    sha3.update(data);
+    let mut output = [0u8; 32];
+    sha3.finalize(&mut output);
+    output
+}
+
+fn blake3_xof(data: &[u8], output_len: usize) -> Vec<u8> {
+    let mut output = vec![0u8; output_len];
+    blake3::Hasher::new().update(data).finalize_xof().fill(&mut output);
+    output
+}
+
+fn hash_chunks<H: Hasher>(mut hasher: H, chunks: &[Vec<u8>], output: &mut [u8]) {
+    for chunk in chunks {
+        hasher.update(chunk);
+    }
+    hasher.finalize(output);
+}
+
+fn keccak_512(data: &[u8]) -> [u8; 64] {
+    let mut keccak = ::tiny_keccak::Keccak::v512();
+    keccak.update(data);
+    let mut output = [0u8; 64];
+    keccak.finalize(&mut output);
+    output
+}
+
+fn sha3_512(data: &[u8]) -> [u8; 64] {
+    let mut sha3 = ::tiny_keccak::Sha3::v512();
+    sha3.update(data);
+    let mut output = [0u8; 64];
+    sha3.finalize(&mut output);
+    output
+}
+
+#[test]
+fn test_u128_order_preserving() {
+    let values = [0u128, 1, 255, 256, 65_535, 1 << 64, u128::MAX - 1, u128::MAX];
+    for pair in values.windows(2) {
+        assert!(u128_order_preserving(pair[0]) < u128_order_preserving(pair[1]));
+    }
+}
+
+#[test]
+fn test_i128_order_preserving() {
+    let values = [i128::MIN, i128::MIN + 1, -256, -1, 0, 1, 256, i128::MAX - 1, i128::MAX];
+    for pair in values.windows(2) {
+        assert!(i128_order_preserving(pair[0]) < i128_order_preserving(pair[1]));
+    }
+    assert_eq!(i128_order_preserving(0)[0], 0x80);
+    let mut minus_one = [0xffu8; 16];
+    minus_one[0] = 0x7f;
+    assert_eq!(i128_order_preserving(-1), minus_one);
+}
+
+#[test]
+fn test_unique_id_depends_on_salt() {
+    // BCS of a struct `{ a: u64, b: bool }` holding `{ 7, true }`
+    let serialized = [7u8, 0, 0, 0, 0, 0, 0, 0, 1];
+
+    let first = unique_id(&serialized, b"first");
+    let second = unique_id(&serialized, b"second");
+    assert_ne!(first, second);
+
+    assert_eq!(first, keccak_256(&[&serialized[..], b"first"].concat()));
+}
+
+#[test]
+fn test_verify_merkle_batch() {
+    let leaves: Vec<Vec<u8>> = (0u8..4).map(|i| keccak_256(&[i]).to_vec()).collect();
+    let left = hash_merkle_pair(&leaves[0], &leaves[1]);
+    let right = hash_merkle_pair(&leaves[2], &leaves[3]);
+    let root = hash_merkle_pair(&left, &right);
+
+    let proof_0 = vec![leaves[1].clone(), right.to_vec()];
+    let proof_3 = vec![leaves[2].clone(), left.to_vec()];
+    assert!(verify_merkle_proof(&leaves[0], &proof_0, &root));
+    assert!(verify_merkle_proof(&leaves[3], &proof_3, &root));
+
+    // a proof for another leaf, an unknown leaf and a truncated proof all fail
+    assert!(!verify_merkle_proof(&leaves[1], &proof_3, &root));
+    assert!(!verify_merkle_proof(&keccak_256(b"unknown"), &proof_0, &root));
+    assert!(!verify_merkle_proof(&leaves[0], &proof_0[..1], &root));
+
+    // two 32 byte leaves with two 32 byte siblings each
+    let proofs = vec![proof_0, proof_3];
+    assert_eq!(merkle_batch_hashed_bytes(&leaves[..2], &proofs), 2 * 32 + 4 * 64);
+    // oversized nodes are charged for their full length
+    let long_proofs = vec![vec![vec![0u8; 1 << 20]]];
+    assert_eq!(
+        merkle_batch_hashed_bytes(&[vec![0u8; 1000]], &long_proofs),
+        1000 + (1 << 20) + 32
+    );
+}
+
+#[test]
+fn test_nested_mapping_slot() {
+    let word = |value: u8| {
+        let mut word = vec![0u8; 32];
+        word[31] = value;
+        word
+    };
+
+    // `mapping(uint256 => ...)` at slot 0: the slot of key 0 is keccak256 of 64 zero bytes.
+    assert_eq!(
+        nested_mapping_slot(&[word(0)], &word(0)),
+        hex::decode("ad3228b676f7d3cd4284a5443f17f1962b36e491b30a40b2405849e597ba5fb5").unwrap(),
+    );
+
+    // `mapping(address => mapping(uint256 => uint256))` at slot 1, read as `m[owner][7]`.
+    let mut owner = vec![0u8; 12];
+    owner.extend_from_slice(&[0xab; 20]);
+    let inner = keccak_256(&[owner.as_slice(), &word(1)].concat());
+    let expected = keccak_256(&[word(7).as_slice(), &inner].concat());
+    assert_eq!(nested_mapping_slot(&[owner, word(7)], &word(1)), expected.to_vec());
+
+    // No keys leave the base slot untouched.
+    assert_eq!(nested_mapping_slot(&[], &word(3)), word(3));
+}
+
+#[test]
+fn test_keccak_512() {
+    assert_eq!(
+        keccak_512(b"").to_vec(),
+        hex::decode(concat!(
+            "0eab42de4c3ceb9235fc91acffe746b29c29a8c366b7c60e4e67c466f36a4304",
+            "c00fa9caf9d87976ba469bcbe06713b435f091ef2769fb160cdab33d3670680e",
+        ))
+        .unwrap(),
+    );
+}
+
+#[test]
+fn test_sha3_512() {
+    assert_eq!(
+        sha3_512(b"").to_vec(),
+        hex::decode(concat!(
+            "a69f73cca23a9ac5c8b567dc185a756e97c982164fe25859e0d1dcc1475c80a6",
+            "15b2123af1f5f94c11e3e9402c3ac558f500199d95b6d3e301758586281dcd26",
+        ))
+        .unwrap(),
+    );
+    assert_eq!(
+        sha3_512(b"abc").to_vec(),
+        hex::decode(concat!(
+            "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e",
+            "10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0",
+        ))
+        .unwrap(),
+    );
+}
+
+#[test]
+fn test_array_element_slot() {
+    // `uint256[]` at slot 0: elements start at keccak256(uint256(0)).
+    let slot = vec![0u8; 32];
+    assert_eq!(
+        array_element_slot(&slot, 0).to_vec(),
+        hex::decode("290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563").unwrap(),
+    );
+    assert_eq!(
+        array_element_slot(&slot, 1).to_vec(),
+        hex::decode("290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e564").unwrap(),
+    );
+    // the carry propagates across bytes
+    assert_eq!(
+        array_element_slot(&slot, 0x1d).to_vec(),
+        hex::decode("290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e580").unwrap(),
+    );
+    assert_eq!(
+        array_element_slot(&slot, u64::MAX).to_vec(),
+        hex::decode("290decd9548b62a8d60345a988386fc84ba6bc95484008f7362f93160ef3e562").unwrap(),
+    );
+}
+
+#[test]
+fn test_blake3() {
+    assert_eq!(
+        blake3_xof(b"", 32),
+        hex::decode("af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262").unwrap(),
+    );
+    assert_eq!(
+        blake3_xof(b"abc", 32),
+        hex::decode("6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85").unwrap(),
+    );
+    // extended output continues the standard digest
+    assert_eq!(
+        blake3_xof(b"", 64),
+        hex::decode(concat!(
+            "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262",
+            "e00f03e7b69af26b7faaf09fcd333050338ddfe085b8cc869ca98b206c08243a",
+        ))
+        .unwrap(),
+    );
+    assert!(blake3_xof(b"abc", 0).is_empty());
+}
+
+#[test]
+fn test_airdrop_leaf() {
+    let account = hex::decode("2c7536e3605d9c16a7a3d7b1898e529396a65c23").unwrap();
+    // abi.encodePacked(uint256(5), account, uint256(1000 * 10**18))
+    let packed = hex::decode(concat!(
+        "0000000000000000000000000000000000000000000000000000000000000005",
+        "2c7536e3605d9c16a7a3d7b1898e529396a65c23",
+        "00000000000000000000000000000000000000000000003635c9adc5dea00000",
+    ))
+    .unwrap();
+    assert_eq!(packed.len(), 84);
+    assert_eq!(airdrop_leaf(5, &account, 1_000_000_000_000_000_000_000), keccak_256(&packed));
+    assert_ne!(airdrop_leaf(6, &account, 1_000_000_000_000_000_000_000), keccak_256(&packed));
+}
+
+#[test]
+fn test_hash_chunks() {
+    let chunks = vec![b"The quick brown ".to_vec(), vec![], b"fox".to_vec()];
+    let joined = chunks.concat();
+
+    let mut output = [0u8; 32];
+    hash_chunks(::tiny_keccak::Keccak::v256(), &chunks, &mut output);
+    assert_eq!(output, keccak_256(&joined));
+
+    let mut output = [0u8; 64];
+    hash_chunks(::tiny_keccak::Keccak::v512(), &chunks, &mut output);
+    assert_eq!(output.to_vec(), keccak_512(&joined).to_vec());
+
+    // no chunks hash like the empty input
+    hash_chunks(::tiny_keccak::Keccak::v512(), &[], &mut output);
+    assert_eq!(output.to_vec(), keccak_512(b"").to_vec());
+}
+
+#[test]
+fn test_chunk_id() {
+    assert_eq!(chunk_id(b"abc"), keccak_256(b"\0\0\0\0\0\0\0\x03abc"));
+    assert_eq!(chunk_id(b""), keccak_256(&[0u8; 8]));
+
+    // "abc" stored as "ab" + "c" or as "a" + "bc" hashes the same without framing ...
+    let content = keccak_256(b"abc");
+    assert_eq!(keccak_256(&[&b"ab"[..], &b"c"[..]].concat()), content);
+    assert_eq!(keccak_256(&[&b"a"[..], &b"bc"[..]].concat()), content);
+    // ... but every chunk gets a distinct id
+    let ids = [chunk_id(b"ab"), chunk_id(b"c"), chunk_id(b"a"), chunk_id(b"bc"), chunk_id(b"abc")];
+    for (i, a) in ids.iter().enumerate() {
+        assert!(ids[i + 1..].iter().all(|b| a != b));
+    }
+    // the length prefix of the empty chunk is not confused with eight zero bytes of data
+    assert_ne!(chunk_id(&[0u8; 8]), chunk_id(b""));
+}
+
+#[test]
+fn test_multiset_hash() {
+    let set = |items: &[&[u8]]| {
+        let mut items: Vec<Vec<u8>> = items.iter().map(|item| item.to_vec()).collect();
+        multiset_hash(&mut items)
+    };
+
+    let votes = set(&[b"yes", b"no", b"yes", b"abstain"]);
+    // order does not matter
+    assert_eq!(set(&[b"no", b"yes", b"abstain", b"yes"]), votes);
+    assert_eq!(set(&[b"abstain", b"yes", b"yes", b"no"]), votes);
+    // multiplicity does
+    assert_ne!(set(&[b"yes", b"no", b"yes", b"abstain", b"yes"]), votes);
+    assert_ne!(set(&[b"yes", b"no", b"abstain"]), votes);
+    // and item boundaries do
+    assert_ne!(set(&[b"ab", b"c"]), set(&[b"a", b"bc"]));
+
+    assert_eq!(set(&[]), [0u8; 32]);
+}
+
+#[test]
+fn test_hashchain() {
+    let seed = b"one time password seed";
+    let third = keccak_256(&keccak_256(&keccak_256(seed)));
+
+    assert_eq!(hashchain(seed, 3), third.to_vec());
+    assert_eq!(hashchain(seed, 0), seed.to_vec());
+    // one link short or one too many
+    assert_ne!(hashchain(seed, 2), third.to_vec());
+    assert_ne!(hashchain(seed, 4), third.to_vec());
+    // revealing the previous link lets the verifier step forward once
+    assert_eq!(hashchain(&hashchain(seed, 2), 1), third.to_vec());
+}
+
+#[test]
+fn test_keccak_packed() {
+    // abi.encodePacked(uint8(7), uint64(0x0102030405060708), uint256(1000 * 10**18))
+    let packed = hex::decode(concat!(
+        "07",
+        "0102030405060708",
+        "00000000000000000000000000000000000000000000003635c9adc5dea00000",
+    ))
+    .unwrap();
+    let values = [7, 0x0102030405060708, 1_000_000_000_000_000_000_000];
+    assert_eq!(keccak_packed(&[1, 8, 32], &values), Ok(keccak_256(&packed)));
+    // the widths are part of the encoding
+    assert_ne!(keccak_packed(&[2, 8, 32], &values), Ok(keccak_256(&packed)));
+    assert_eq!(keccak_packed(&[], &[]), Ok(keccak_256(&[])));
+
+    assert_eq!(keccak_packed(&[1, 8], &values), Err(EPACKED_LENGTH_MISMATCH));
+    assert_eq!(keccak_packed(&[0], &[0]), Err(EPACKED_INVALID_WIDTH));
+    assert_eq!(keccak_packed(&[33], &[1]), Err(EPACKED_INVALID_WIDTH));
+    // 256 does not fit in a uint8
+    assert_eq!(keccak_packed(&[1], &[256]), Err(EPACKED_INVALID_WIDTH));
+    assert!(keccak_packed(&[1], &[255]).is_ok());
+}
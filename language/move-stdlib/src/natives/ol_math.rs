@@ -0,0 +1,255 @@
+//////// 0L ////////
+// SPDX-License-Identifier: Apache-2.0
+use ethers::core::types::U256;
+use move_binary_format::errors::PartialVMResult;
+use move_vm_runtime::native_functions::NativeContext;
+use move_vm_types::{
+    gas_schedule::NativeCostIndex,
+    loaded_data::runtime_types::Type,
+    natives::function::{native_gas, NativeResult},
+    pop_arg,
+    values::Value,
+};
+use smallvec::smallvec;
+use std::collections::VecDeque;
+
+/// Abort code when a zero denominator is supplied.
+const EZERO_DENOMINATOR: u64 = 1;
+/// Abort code when the result does not fit in a u128.
+const EOVERFLOW: u64 = 2;
+
+/// Rust implementation of Move's `native public fun lerp(a: u128, b: u128, t_num: u128,
+/// t_den: u128): u128`
+pub fn native_lerp_u128(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 4);
+
+    // pop arguments in reverse order
+    let t_den = pop_arg!(arguments, u128);
+    let t_num = pop_arg!(arguments, u128);
+    let b = pop_arg!(arguments, u128);
+    let a = pop_arg!(arguments, u128);
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::DECIMAL, 1)?;
+
+    if t_den == 0 {
+        return Ok(NativeResult::err(cost, EZERO_DENOMINATOR));
+    }
+
+    match lerp(a, b, t_num, t_den) {
+        Some(result) => Ok(NativeResult::ok(cost, smallvec![Value::u128(result)])),
+        None => Ok(NativeResult::err(cost, EOVERFLOW)),
+    }
+}
+
+// Computes `a + (b - a) * t_num / t_den`. The product is taken in 256 bits so
+// only a result outside of the u128 range can fail.
+fn lerp(a: u128, b: u128, t_num: u128, t_den: u128) -> Option<u128> {
+    let (distance, forward) = if b >= a { (b - a, true) } else { (a - b, false) };
+    let step = U256::from(distance) * U256::from(t_num) / U256::from(t_den);
+    if step > U256::from(u128::MAX) {
+        return None;
+    }
+    let step = step.as_u128();
+    if forward {
+        a.checked_add(step)
+    } else {
+        a.checked_sub(step)
+    }
+}
+
+/// Rust implementation of Move's `native public fun bit_length(value: u128): u8`
+/// Returns the position of the highest set bit plus one, and 0 for zero.
+pub fn native_bit_length(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let value = pop_arg!(arguments, u128);
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::DECIMAL, 1)?;
+
+    Ok(NativeResult::ok(cost, smallvec![Value::u8(bit_length(value))]))
+}
+
+fn bit_length(value: u128) -> u8 {
+    (128 - value.leading_zeros()) as u8
+}
+
+/// Rust implementation of Move's `native public fun saturating_add(a: u128, b: u128): u128`
+/// Clamps to `u128::MAX` instead of aborting on overflow.
+pub fn native_saturating_add(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let b = pop_arg!(arguments, u128);
+    let a = pop_arg!(arguments, u128);
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::DECIMAL, 1)?;
+
+    Ok(NativeResult::ok(cost, smallvec![Value::u128(saturating_add(a, b))]))
+}
+
+fn saturating_add(a: u128, b: u128) -> u128 {
+    a.saturating_add(b)
+}
+
+/// Rust implementation of Move's `native public fun saturating_sub(a: u128, b: u128): u128`
+/// Clamps to 0 instead of aborting on underflow.
+pub fn native_saturating_sub(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let b = pop_arg!(arguments, u128);
+    let a = pop_arg!(arguments, u128);
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::DECIMAL, 1)?;
+
+    Ok(NativeResult::ok(cost, smallvec![Value::u128(saturating_sub(a, b))]))
+}
+
+fn saturating_sub(a: u128, b: u128) -> u128 {
+    a.saturating_sub(b)
+}
+
+/// Rust implementation of Move's `native public fun align_up(x: u128, align: u128): (bool, u128)`
+/// Rounds `x` up to the next multiple of `align`. The flag is false, with a zero result, when
+/// `align` is zero or the multiple does not fit in a u128.
+pub fn native_align_up(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let align = pop_arg!(arguments, u128);
+    let x = pop_arg!(arguments, u128);
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::DECIMAL, 1)?;
+
+    let (ok, aligned) = match align_up(x, align) {
+        Some(aligned) => (true, aligned),
+        None => (false, 0),
+    };
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::bool(ok), Value::u128(aligned)],
+    ))
+}
+
+fn align_up(x: u128, align: u128) -> Option<u128> {
+    let remainder = x.checked_rem(align)?;
+    if remainder == 0 {
+        Some(x)
+    } else {
+        x.checked_add(align - remainder)
+    }
+}
+
+/// Rust implementation of Move's `native public fun checked_sum(values: vector<u128>):
+/// (bool, u128)`
+/// Sums `values`. The flag is false, with a zero result, when the sum does not fit in a u128.
+pub fn native_checked_sum(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let values = pop_arg!(arguments, Vec<u128>);
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::DECIMAL, values.len())?;
+
+    let (ok, sum) = match checked_sum(&values) {
+        Some(sum) => (true, sum),
+        None => (false, 0),
+    };
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::bool(ok), Value::u128(sum)],
+    ))
+}
+
+fn checked_sum(values: &[u128]) -> Option<u128> {
+    values.iter().try_fold(0u128, |sum, value| sum.checked_add(*value))
+}
+
+#[test]
+fn test_lerp() {
+    assert_eq!(lerp(10, 20, 0, 1), Some(10));
+    assert_eq!(lerp(10, 20, 1, 1), Some(20));
+    assert_eq!(lerp(10, 20, 1, 2), Some(15));
+    // decreasing ranges interpolate downwards
+    assert_eq!(lerp(20, 10, 1, 2), Some(15));
+    // the intermediate product does not overflow
+    assert_eq!(lerp(0, u128::MAX, u128::MAX, u128::MAX), Some(u128::MAX));
+    // extrapolating past the range does
+    assert_eq!(lerp(0, u128::MAX, 2, 1), None);
+}
+
+#[test]
+fn test_bit_length() {
+    assert_eq!(bit_length(0), 0);
+    assert_eq!(bit_length(1), 1);
+    assert_eq!(bit_length(255), 8);
+    assert_eq!(bit_length(256), 9);
+    assert_eq!(bit_length(1 << 64), 65);
+    assert_eq!(bit_length(u128::MAX), 128);
+}
+
+#[test]
+fn test_saturating_arithmetic() {
+    assert_eq!(saturating_add(1, 2), 3);
+    assert_eq!(saturating_add(u128::MAX - 1, 1), u128::MAX);
+    assert_eq!(saturating_add(u128::MAX, 1), u128::MAX);
+    assert_eq!(saturating_add(u128::MAX, u128::MAX), u128::MAX);
+    assert_eq!(saturating_sub(5, 2), 3);
+    assert_eq!(saturating_sub(1, 1), 0);
+    assert_eq!(saturating_sub(0, 1), 0);
+    assert_eq!(saturating_sub(0, u128::MAX), 0);
+}
+
+#[test]
+fn test_align_up() {
+    // already aligned
+    assert_eq!(align_up(0, 8), Some(0));
+    assert_eq!(align_up(16, 8), Some(16));
+    assert_eq!(align_up(u128::MAX, u128::MAX), Some(u128::MAX));
+    // needs rounding
+    assert_eq!(align_up(17, 8), Some(24));
+    assert_eq!(align_up(1, 100), Some(100));
+    assert_eq!(align_up(u128::MAX, 2), None);
+    // zero alignment
+    assert_eq!(align_up(17, 0), None);
+}
+
+#[test]
+fn test_checked_sum() {
+    assert_eq!(checked_sum(&[]), Some(0));
+    assert_eq!(checked_sum(&[1, 2, 3]), Some(6));
+    // reaching exactly the maximum is fine
+    assert_eq!(checked_sum(&[u128::MAX - 10, 7, 3]), Some(u128::MAX));
+    assert_eq!(checked_sum(&[u128::MAX, 0]), Some(u128::MAX));
+    // one past it is not, even if later values are zero
+    assert_eq!(checked_sum(&[u128::MAX - 10, 7, 4]), None);
+    assert_eq!(checked_sum(&[u128::MAX, 1, 0]), None);
+}
@@ -0,0 +1,211 @@
+//////// 0L ////////
+// SPDX-License-Identifier: Apache-2.0
+use crate::natives::ol_hash::keccak_256;
+use move_binary_format::errors::PartialVMResult;
+use move_vm_runtime::native_functions::NativeContext;
+use move_vm_types::{
+    gas_schedule::NativeCostIndex,
+    loaded_data::runtime_types::Type,
+    natives::function::{native_gas, NativeResult},
+    pop_arg,
+    values::Value,
+};
+use smallvec::smallvec;
+use std::collections::VecDeque;
+
+/// Abort code when a key or hash is not 32 bytes.
+const EINVALID_HASH_LENGTH: u64 = 1;
+
+/// Domain prefix of sparse Merkle tree leaf hashes.
+const SMT_LEAF_PREFIX: u8 = 0x00;
+/// Domain prefix of sparse Merkle tree internal node hashes.
+const SMT_INTERNAL_PREFIX: u8 = 0x01;
+/// Hash of an empty subtree.
+const SMT_EMPTY_HASH: [u8; 32] = [0u8; 32];
+
+/// Rust implementation of Move's `native public fun smt_leaf_hash(key: vector<u8>,
+/// value_hash: vector<u8>): vector<u8>`
+/// Computes `keccak256(0x00 || key || value_hash)`. Both inputs are 32 bytes.
+pub fn native_smt_leaf_hash(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let value_hash = pop_arg!(arguments, Vec<u8>);
+    let key = pop_arg!(arguments, Vec<u8>);
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::KECCAK_256, 65)?;
+
+    if key.len() != 32 || value_hash.len() != 32 {
+        return Ok(NativeResult::err(cost, EINVALID_HASH_LENGTH));
+    }
+    let hash = smt_leaf_hash(&key, &value_hash).to_vec();
+    Ok(NativeResult::ok(cost, smallvec![Value::vector_u8(hash)]))
+}
+
+/// Rust implementation of Move's `native public fun smt_internal_hash(left: vector<u8>,
+/// right: vector<u8>): vector<u8>`
+/// Computes `keccak256(0x01 || left || right)`. Both children are 32 bytes.
+pub fn native_smt_internal_hash(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let right = pop_arg!(arguments, Vec<u8>);
+    let left = pop_arg!(arguments, Vec<u8>);
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::KECCAK_256, 65)?;
+
+    if left.len() != 32 || right.len() != 32 {
+        return Ok(NativeResult::err(cost, EINVALID_HASH_LENGTH));
+    }
+    let hash = smt_internal_hash(&left, &right).to_vec();
+    Ok(NativeResult::ok(cost, smallvec![Value::vector_u8(hash)]))
+}
+
+/// Rust implementation of Move's `native public fun verify_non_inclusion(key: vector<u8>,
+/// proof: vector<vector<u8>>, root: vector<u8>): bool`
+/// `proof[0]` is where the search for `key` ends: either empty, for an empty subtree, or the
+/// 64 byte `other_key || value_hash` of a leaf holding a different key with the same path.
+/// The rest of `proof` are the sibling hashes from that node up to the root. The path follows
+/// the bits of `key`, most significant first, with a set bit going right.
+/// Malformed proofs verify as false.
+pub fn native_smt_verify_non_inclusion(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
+
+    let root = pop_arg!(arguments, Vec<u8>);
+    let proof = pop_arg!(arguments, Vec<Value>)
+        .into_iter()
+        .map(|node| node.value_as::<Vec<u8>>())
+        .collect::<PartialVMResult<Vec<_>>>()?;
+    let key = pop_arg!(arguments, Vec<u8>);
+
+    // at most one leaf and one internal hash per proof entry
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::KECCAK_256,
+        65 * proof.len(),
+    )?;
+
+    let verified = smt_verify_non_inclusion(&key, &proof, &root);
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(verified)]))
+}
+
+fn smt_verify_non_inclusion(key: &[u8], proof: &[Vec<u8>], root: &[u8]) -> bool {
+    let (terminal, siblings) = match proof.split_first() {
+        Some(split) => split,
+        None => return false,
+    };
+    let depth = siblings.len();
+    if key.len() != 32 || root.len() != 32 || depth > 256 {
+        return false;
+    }
+    if siblings.iter().any(|sibling| sibling.len() != 32) {
+        return false;
+    }
+
+    let mut hash = match terminal.len() {
+        0 => SMT_EMPTY_HASH,
+        64 => {
+            let (other_key, value_hash) = terminal.split_at(32);
+            // the leaf must sit on the path of `key` without being `key`
+            if other_key == key || (0..depth).any(|d| key_bit(other_key, d) != key_bit(key, d)) {
+                return false;
+            }
+            smt_leaf_hash(other_key, value_hash)
+        }
+        _ => return false,
+    };
+
+    for (d, sibling) in (0..depth).rev().zip(siblings) {
+        hash = if key_bit(key, d) {
+            smt_internal_hash(sibling, &hash)
+        } else {
+            smt_internal_hash(&hash, sibling)
+        };
+    }
+    hash[..] == root[..]
+}
+
+fn key_bit(key: &[u8], depth: usize) -> bool {
+    key[depth / 8] & (0x80 >> (depth % 8)) != 0
+}
+
+fn smt_leaf_hash(key: &[u8], value_hash: &[u8]) -> [u8; 32] {
+    prefixed_hash(SMT_LEAF_PREFIX, key, value_hash)
+}
+
+fn smt_internal_hash(left: &[u8], right: &[u8]) -> [u8; 32] {
+    prefixed_hash(SMT_INTERNAL_PREFIX, left, right)
+}
+
+fn prefixed_hash(prefix: u8, first: &[u8], second: &[u8]) -> [u8; 32] {
+    keccak_256(&[&[prefix][..], first, second].concat())
+}
+
+#[test]
+fn test_smt_domain_separation() {
+    let a = [0x11u8; 32];
+    let b = [0x22u8; 32];
+
+    // the same bytes hash differently as a leaf and as an internal node
+    assert_ne!(smt_leaf_hash(&a, &b), smt_internal_hash(&a, &b));
+    assert_ne!(smt_leaf_hash(&a, &a), smt_internal_hash(&a, &a));
+    // and neither is the bare hash of the concatenation
+    let bare = keccak_256(&[a, b].concat());
+    assert_ne!(smt_leaf_hash(&a, &b), bare);
+    assert_ne!(smt_internal_hash(&a, &b), bare);
+
+    // children are ordered
+    assert_ne!(smt_internal_hash(&a, &b), smt_internal_hash(&b, &a));
+}
+
+#[test]
+fn test_smt_verify_non_inclusion() {
+    let key = |first: u8| {
+        let mut key = [0x5a; 32];
+        key[0] = first;
+        key
+    };
+    let (k00, k01, k10) = (key(0x00), key(0x40), key(0x80));
+    let (v00, v01) = ([0xa0; 32], [0xa1; 32]);
+    let leaf = |k: &[u8; 32], v: &[u8; 32]| [&k[..], &v[..]].concat();
+
+    // k00 and k01 split at the second bit, the right half of the tree is empty
+    let left = smt_internal_hash(&smt_leaf_hash(&k00, &v00), &smt_leaf_hash(&k01, &v01));
+    let root = smt_internal_hash(&left, &SMT_EMPTY_HASH).to_vec();
+
+    // k10 falls in the empty right subtree
+    let proof = vec![vec![], left.to_vec()];
+    assert!(smt_verify_non_inclusion(&k10, &proof, &root));
+
+    // a key under the left subtree lands on the k00 leaf, which is another key
+    let absent = key(0x20);
+    let proof = vec![leaf(&k00, &v00), smt_leaf_hash(&k01, &v01).to_vec(), SMT_EMPTY_HASH.to_vec()];
+    assert!(smt_verify_non_inclusion(&absent, &proof, &root));
+
+    // k00 itself is present, so the same proof does not show its absence
+    assert!(!smt_verify_non_inclusion(&k00, &proof, &root));
+    // nor does a leaf off the path of the key
+    let proof = vec![leaf(&k01, &v01), smt_leaf_hash(&k00, &v00).to_vec(), SMT_EMPTY_HASH.to_vec()];
+    assert!(!smt_verify_non_inclusion(&absent, &proof, &root));
+
+    // claiming the occupied left subtree is empty fails against the root
+    let proof = vec![vec![], SMT_EMPTY_HASH.to_vec()];
+    assert!(!smt_verify_non_inclusion(&absent, &proof, &root));
+    // malformed proofs
+    assert!(!smt_verify_non_inclusion(&k10, &[], &root));
+    assert!(!smt_verify_non_inclusion(&k10, &[vec![], left[..31].to_vec()], &root));
+    assert!(!smt_verify_non_inclusion(&k10[..31], &[vec![], left.to_vec()], &root));
+}
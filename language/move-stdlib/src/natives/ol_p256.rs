@@ -0,0 +1,110 @@
+//////// 0L ////////
+// SPDX-License-Identifier: Apache-2.0
+use move_binary_format::errors::PartialVMResult;
+use move_vm_runtime::native_functions::NativeContext;
+use move_vm_types::{
+    gas_schedule::NativeCostIndex,
+    loaded_data::runtime_types::Type,
+    natives::function::{native_gas, NativeResult},
+    pop_arg,
+    values::Value,
+};
+use p256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+use sha2::{Digest, Sha256};
+use smallvec::smallvec;
+use std::collections::VecDeque;
+
+/// rpIdHash (32 bytes), flags (1 byte) and signCount (4 bytes).
+const MIN_AUTHENTICATOR_DATA_LEN: usize = 37;
+
+/// Rust implementation of Move's `native public fun webauthn_verify(pubkey: vector<u8>,
+/// authenticator_data: vector<u8>, client_data_json: vector<u8>, sig: vector<u8>): bool`
+/// Verifies an ES256 WebAuthn assertion: a P-256 signature over
+/// `authenticator_data || sha256(client_data_json)`. `pubkey` is a SEC1 encoded point and
+/// `sig` is DER encoded, as returned by the authenticator.
+/// Malformed keys, signatures, authenticator data or an empty client data verify as false.
+pub fn native_webauthn_verify(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 4);
+
+    let sig = pop_arg!(arguments, Vec<u8>);
+    let client_data_json = pop_arg!(arguments, Vec<u8>);
+    let authenticator_data = pop_arg!(arguments, Vec<u8>);
+    let pubkey = pop_arg!(arguments, Vec<u8>);
+
+    // a P-256 check costs about as much as a secp256k1 one, see the shared slots
+    // documented on `NativeCostIndex`
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::ETH_SIGNATURE_VERIFY,
+        authenticator_data.len() + client_data_json.len(),
+    )?;
+
+    let verified = webauthn_verify(&pubkey, &authenticator_data, &client_data_json, &sig);
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(verified)]))
+}
+
+fn webauthn_verify(
+    pubkey: &[u8],
+    authenticator_data: &[u8],
+    client_data_json: &[u8],
+    sig: &[u8],
+) -> bool {
+    if authenticator_data.len() < MIN_AUTHENTICATOR_DATA_LEN || client_data_json.is_empty() {
+        return false;
+    }
+    let key = match VerifyingKey::from_sec1_bytes(pubkey) {
+        Ok(key) => key,
+        Err(_) => return false,
+    };
+    let sig = match Signature::from_der(sig) {
+        Ok(sig) => sig,
+        Err(_) => return false,
+    };
+
+    let mut message = authenticator_data.to_vec();
+    message.extend_from_slice(&Sha256::digest(client_data_json));
+    // hashes the message with SHA-256 before verifying
+    key.verify(&message, &sig).is_ok()
+}
+
+#[test]
+fn test_webauthn_verify() {
+    // assertion for rpId "example.com", flags UP | UV, signCount 1
+    let pubkey = hex::decode(
+        "042b55e1574484ad9061b4ce979dd1806cfe60f6a51ecfed37d983262469024fcd\
+         2581f8f570d48aab099576e2c29d1493c13ac6fa27e4ad6b6d5dd9cac08beb8b",
+    )
+    .unwrap();
+    let authenticator_data =
+        hex::decode("a379a6f6eeafb9a55e378c118034e2751e682fab9f2d30ab13d2125586ce19470500000001")
+            .unwrap();
+    let client_data_json = concat!(
+        r#"{"type":"webauthn.get","challenge":"MEwgcGFzc2tleSBsb2dpbg","#,
+        r#""origin":"https://example.com","crossOrigin":false}"#,
+    )
+    .as_bytes();
+    let sig = hex::decode(
+        "3046022100cf5065673ecc010f3394033c940d269e266c9e16107f67a90026184e68e08fe0\
+         022100e8909d2f62fbee5501cfd8d4c3546b07d3d9b7fcc89cf7fd2c6f2233a020c83a",
+    )
+    .unwrap();
+    assert!(webauthn_verify(&pubkey, &authenticator_data, client_data_json, &sig));
+
+    // a different client data or counter no longer verifies
+    let tampered = client_data_json[..client_data_json.len() - 1].to_vec();
+    assert!(!webauthn_verify(&pubkey, &authenticator_data, &tampered, &sig));
+    let mut bumped = authenticator_data.clone();
+    bumped[36] = 2;
+    assert!(!webauthn_verify(&pubkey, &bumped, client_data_json, &sig));
+
+    // malformed inputs
+    assert!(!webauthn_verify(&pubkey, &authenticator_data[..36], client_data_json, &sig));
+    assert!(!webauthn_verify(&pubkey, &authenticator_data, b"", &sig));
+    assert!(!webauthn_verify(&pubkey[1..], &authenticator_data, client_data_json, &sig));
+    assert!(!webauthn_verify(&pubkey, &authenticator_data, client_data_json, &sig[..70]));
+}
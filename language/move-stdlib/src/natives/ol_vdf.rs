@@ -1,7 +1,13 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 use vdf::{VDFParams, VDF};
-use move_core_types::{vm_status::StatusCode, account_address::AccountAddress};
+use move_core_types::{
+    account_address::AccountAddress,
+    gas_schedule::{CostTable, GasAlgebra, GasCarrier, InternalGasUnits},
+    vm_status::StatusCode,
+};
+#[cfg(feature = "testing")]
+use move_core_types::gas_schedule::ONE_GAS_UNIT;
 use move_vm_runtime::native_functions::NativeContext;
 use move_vm_types::{
     gas_schedule::NativeCostIndex,
@@ -10,7 +16,7 @@ use move_vm_types::{
     pop_arg,
     values::{Reference, Value},
 };
-use std::collections::VecDeque;
+use std::{collections::VecDeque, convert::TryFrom, time::Instant};
 use move_binary_format::errors::{PartialVMError, PartialVMResult};
 use smallvec::smallvec;
 use crate::natives::ol_counters::{
@@ -19,15 +25,77 @@ use crate::natives::ol_counters::{
     MOVE_VM_NATIVE_VERIFY_VDF_PROOF_ERROR_COUNT
 };
 
+/// Parameters of the VDF natives that are not gas costs.
+/// They are fixed when the native table is built, see `all_natives_with_vdf_parameters`,
+/// so every validator running the same build applies the same ones and the on-chain gas
+/// schedule keeps its layout.
+pub trait VdfGasParameters {
+    /// The largest security parameter accepted. Larger parameters make proofs too
+    /// expensive to verify and are refused to protect validators.
+    const MAX_SECURITY: u16;
+}
+
+/// The parameters of the natives registered by `all_natives`.
+pub struct DefaultVdfGasParameters;
+
+impl VdfGasParameters for DefaultVdfGasParameters {
+    const MAX_SECURITY: u16 = 2048;
+}
+
+fn check_security(security: u64, max_security: u16) -> PartialVMResult<u16> {
+    match u16::try_from(security) {
+        Ok(security) if security <= max_security => Ok(security),
+        _ => Err(
+            PartialVMError::new(StatusCode::UNREACHABLE).with_message(
+              "VDF security parameter above threshold".to_string()
+            )
+        ),
+    }
+}
+
+/// `scheme` value selecting the Pietrzak construction, the one used by `verify`.
+const VDF_SCHEME_PIETRZAK: u8 = 0;
+/// `scheme` value selecting the Wesolowski construction.
+const VDF_SCHEME_WESOLOWSKI: u8 = 1;
+
 /// Rust implementation of Move's `native public fun verify(challenge: vector<u8>, 
 /// difficulty: u64, alleged_solution: vector<u8>): bool`
-pub fn native_verify(
+pub fn native_verify<P: VdfGasParameters>(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    verify_with_scheme(context, arguments, VDF_SCHEME_PIETRZAK, P::MAX_SECURITY)
+}
+
+/// Rust implementation of Move's `native public fun verify_with_scheme(challenge: &vector<u8>,
+/// alleged_solution: &vector<u8>, difficulty: &u64, security: &u64, scheme: u8): bool`
+/// `scheme` is 0 for Pietrzak and 1 for Wesolowski.
+pub fn native_verify_with_scheme<P: VdfGasParameters>(
     context: &mut NativeContext,
     _ty_args: Vec<Type>,
     mut arguments: VecDeque<Value>,
 ) -> PartialVMResult<NativeResult> {
-    // temporary logging.
-    // let start_time = Instant::now();
+    if arguments.len() != 5 {
+        let msg = format!(
+            "wrong number of arguments for vdf_verify_with_scheme expected 5 found {}",
+            arguments.len()
+        );
+        MOVE_VM_NATIVE_VERIFY_VDF_PROOF_ERROR_COUNT.inc();
+        return Err(PartialVMError::new(StatusCode::UNREACHABLE).with_message(msg));
+    }
+
+    let scheme = pop_arg!(arguments, u8);
+    verify_with_scheme(context, arguments, scheme, P::MAX_SECURITY)
+}
+
+fn verify_with_scheme(
+    context: &mut NativeContext,
+    mut arguments: VecDeque<Value>,
+    scheme: u8,
+    max_security: u16,
+) -> PartialVMResult<NativeResult> {
+    let start_time = Instant::now();
     let metric_timer = MOVE_VM_NATIVE_VERIFY_VDF_LATENCY.start_timer();
     
     if arguments.len() != 4 {
@@ -46,32 +114,147 @@ pub fn native_verify(
     let solution = pop_arg!(arguments, Reference).read_ref()?.value_as::<Vec<u8>>()?;
     let challenge = pop_arg!(arguments, Reference).read_ref()?.value_as::<Vec<u8>>()?;
 
-    // refuse to try anything with a security parameter above 2048 for DOS risk.
-    if security > 2048 {
-        MOVE_VM_NATIVE_VERIFY_VDF_PROOF_ERROR_COUNT.inc();
-        return Err(
-            PartialVMError::new(StatusCode::UNREACHABLE).with_message(
-              "VDF security parameter above threshold".to_string()
-            )
-        );
-    }
+    // refuse to try anything with a security parameter above the ceiling for DOS risk.
+    let security = match check_security(security, max_security) {
+        Ok(security) => security,
+        Err(e) => {
+            MOVE_VM_NATIVE_VERIFY_VDF_PROOF_ERROR_COUNT.inc();
+            return Err(e);
+        }
+    };
 
-    // TODO change the `cost_index` when we have our own cost table.
-    let cost = native_gas(context.cost_table(), NativeCostIndex::VDF_VERIFY, 1);
+    let cost = verify_cost(context.cost_table(), scheme, difficulty)?;
 
-    let v = vdf::PietrzakVDFParams(security as u16).new();
-    let result = v.verify(&challenge, difficulty, &solution);
+    let verified = match verify(scheme, &challenge, &solution, difficulty, security) {
+        Some(verified) => verified,
+        None => {
+            MOVE_VM_NATIVE_VERIFY_VDF_PROOF_ERROR_COUNT.inc();
+            return Err(PartialVMError::new(StatusCode::UNREACHABLE)
+                .with_message(format!("unknown VDF scheme {}", scheme)));
+        }
+    };
 
-    let return_values = smallvec![Value::bool(result.is_ok())];
+    let return_values = smallvec![Value::bool(verified)];
 
-    // temporary logging
-    // let latency = start_time.elapsed();
     metric_timer.observe_duration();
-    // dbg!("vdf verification latency", &latency);
-    
+    tracing::trace!(
+        target: "move_vm::natives",
+        latency_us = start_time.elapsed().as_micros() as u64,
+        scheme,
+        difficulty,
+        security,
+        verified,
+        "vdf verification"
+    );
+
     Ok(NativeResult::ok(cost, return_values))
 }
 
+/// Abort code of `prove` when the difficulty is rejected by the VDF or above
+/// `MAX_PROVE_DIFFICULTY`.
+#[cfg(feature = "testing")]
+const EVDF_INVALID_DIFFICULTY: u64 = 1;
+
+/// Largest difficulty `prove` accepts, so a test cannot stall the suite on a mainnet-sized proof.
+#[cfg(feature = "testing")]
+const MAX_PROVE_DIFFICULTY: u64 = 1_000_000;
+
+/// Rust implementation of Move's `native public fun prove(challenge: vector<u8>,
+/// difficulty: u64, security: u64): vector<u8>`
+/// Only available in tests, it produces a Pietrzak proof accepted by `verify`. Production
+/// builds, without the `testing` feature, do not register it.
+#[cfg(feature = "testing")]
+pub fn native_prove<P: VdfGasParameters>(
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
+
+    // pop the arguments (reverse order).
+    let security = pop_arg!(arguments, u64);
+    let difficulty = pop_arg!(arguments, u64);
+    let challenge = pop_arg!(arguments, Vec<u8>);
+
+    // same ceiling as `verify`.
+    let security = check_security(security, P::MAX_SECURITY)?;
+
+    match prove(&challenge, difficulty, security) {
+        Some(proof) => Ok(NativeResult::ok(ONE_GAS_UNIT, smallvec![Value::vector_u8(proof)])),
+        None => Ok(NativeResult::err(ONE_GAS_UNIT, EVDF_INVALID_DIFFICULTY)),
+    }
+}
+
+#[cfg(feature = "testing")]
+fn prove(challenge: &[u8], difficulty: u64, security: u16) -> Option<Vec<u8>> {
+    if difficulty > MAX_PROVE_DIFFICULTY {
+        return None;
+    }
+    vdf::PietrzakVDFParams(security)
+        .new()
+        .solve(challenge, difficulty)
+        .ok()
+}
+
+/// Mainnet difficulty. One `VDF_VERIFY` unit pays for a proof of this difficulty.
+const VDF_BASELINE_DIFFICULTY: u64 = 120_000_000;
+
+// Gas for verifying a proof of `difficulty` under `scheme`, scaled from the `VDF_VERIFY`
+// unit charged at `VDF_BASELINE_DIFFICULTY`.
+// A Pietrzak proof carries one intermediate value per halving of the difficulty and the
+// verifier does a fixed size group exponentiation for each, so the cost is proportional to
+// log2(difficulty): 2e8 costs 28/27 of 1e8. A Wesolowski verifier does two group
+// exponentiations whose size depends only on the security parameter; the difficulty only
+// enters through 2^difficulty mod a small prime, which is negligible next to them, so it is
+// charged the flat unit.
+fn verify_cost(
+    cost_table: &CostTable,
+    scheme: u8,
+    difficulty: u64,
+) -> PartialVMResult<InternalGasUnits<GasCarrier>> {
+    let unit = native_gas(cost_table, NativeCostIndex::VDF_VERIFY, 1)?;
+    if scheme == VDF_SCHEME_WESOLOWSKI {
+        return Ok(unit);
+    }
+    let cost = unit.get() as u128 * halvings(difficulty) as u128
+        / halvings(VDF_BASELINE_DIFFICULTY) as u128;
+    Ok(InternalGasUnits::new(std::cmp::min(cost, GasCarrier::MAX as u128) as GasCarrier))
+}
+
+// Number of halving rounds of a Pietrzak proof, at least one.
+fn halvings(difficulty: u64) -> u64 {
+    std::cmp::max(1, 64 - difficulty.leading_zeros() as u64)
+}
+
+// Returns `None` for an unknown scheme.
+fn verify(
+    scheme: u8,
+    challenge: &[u8],
+    solution: &[u8],
+    difficulty: u64,
+    security: u16,
+) -> Option<bool> {
+    let result = match scheme {
+        VDF_SCHEME_PIETRZAK => vdf::PietrzakVDFParams(security)
+            .new()
+            .verify(challenge, difficulty, solution),
+        VDF_SCHEME_WESOLOWSKI => vdf::WesolowskiVDFParams(security)
+            .new()
+            .verify(challenge, difficulty, solution),
+        _ => return None,
+    };
+    Some(result.is_ok())
+}
+
+/// Abort code of `extract_address_from_challenge` when the challenge is shorter than an
+/// authentication key.
+const EVDF_CHALLENGE_TOO_SHORT: u64 = 2;
+
+// We want to use Diem AuthenticationKey::derived_address() here but this creates 
+// libra (and as a result cyclic) dependency which we definitely do not want
+const AUTHENTICATION_KEY_LENGTH: usize = 32;
+
 // Extracts the first 32 bits of the vdf challenge which is the auth_key
 // Auth Keys can be turned into an AccountAddress type, to be serialized to 
 // a move address type.
@@ -82,20 +265,144 @@ pub fn native_extract_address_from_challenge(
 ) -> PartialVMResult<NativeResult> {
     let challenge_vec = pop_arg!(arguments, Reference).read_ref()?.value_as::<Vec<u8>>()?;
 
-    // We want to use Diem AuthenticationKey::derived_address() here but this creates 
-    // libra (and as a result cyclic) dependency which we definitely do not want
-    const AUTHENTICATION_KEY_LENGTH: usize = 32;
-    let auth_key_vec = &challenge_vec[..AUTHENTICATION_KEY_LENGTH];
+    let cost = native_gas(context.cost_table(), NativeCostIndex::VDF_PARSE, 1)?;
+    let (address, auth_key_prefix) = match extract_address_from_challenge(&challenge_vec) {
+        Some(extracted) => extracted,
+        None => return Ok(NativeResult::err(cost, EVDF_CHALLENGE_TOO_SHORT)),
+    };
+
+    let return_values = smallvec![
+        Value::address(address), Value::vector_u8(auth_key_prefix)
+    ];
+    Ok(NativeResult::ok(cost, return_values))
+}
+
+// `None` if the challenge does not start with a full authentication key.
+fn extract_address_from_challenge(challenge: &[u8]) -> Option<(AccountAddress, Vec<u8>)> {
+    let auth_key_vec = challenge.get(..AUTHENTICATION_KEY_LENGTH)?;
     // Address derived from the last `AccountAddress::LENGTH` bytes of authentication key
     let mut array = [0u8; AccountAddress::LENGTH];
     array.copy_from_slice(
         &auth_key_vec[AUTHENTICATION_KEY_LENGTH - AccountAddress::LENGTH..]
     );
-    let address = AccountAddress::new(array);
+    Some((AccountAddress::new(array), auth_key_vec[..16].to_owned()))
+}
 
-    let return_values = smallvec![
-        Value::address(address), Value::vector_u8(auth_key_vec[..16].to_owned())
-    ];
-    let cost = native_gas(context.cost_table(), NativeCostIndex::VDF_PARSE, 1);
-    Ok(NativeResult::ok(cost, return_values))
-}
\ No newline at end of file
+#[test]
+fn test_wesolowski_verify() {
+    let challenge = b"wesolowski challenge";
+    let (difficulty, security) = (100, 512);
+    let proof = vdf::WesolowskiVDFParams(security)
+        .new()
+        .solve(challenge, difficulty)
+        .unwrap();
+
+    assert_eq!(
+        verify(VDF_SCHEME_WESOLOWSKI, challenge, &proof, difficulty, security),
+        Some(true)
+    );
+
+    let mut malformed = proof.clone();
+    malformed[0] ^= 1;
+    assert_eq!(
+        verify(VDF_SCHEME_WESOLOWSKI, challenge, &malformed, difficulty, security),
+        Some(false)
+    );
+
+    // a proof only verifies under the scheme that produced it
+    assert_eq!(verify(VDF_SCHEME_PIETRZAK, challenge, &proof, difficulty, security), Some(false));
+    assert_eq!(verify(2, challenge, &proof, difficulty, security), None);
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn test_prove_then_verify() {
+    let challenge = b"pietrzak challenge";
+    let (difficulty, security) = (100, 512);
+    let proof = prove(challenge, difficulty, security).unwrap();
+
+    assert_eq!(verify(VDF_SCHEME_PIETRZAK, challenge, &proof, difficulty, security), Some(true));
+    assert_eq!(
+        verify(VDF_SCHEME_PIETRZAK, b"other challenge", &proof, difficulty, security),
+        Some(false)
+    );
+    // difficulties above the bound are refused without solving
+    assert_eq!(prove(challenge, MAX_PROVE_DIFFICULTY + 1, security), None);
+}
+
+#[test]
+fn test_security_ceiling() {
+    let max_security = DefaultVdfGasParameters::MAX_SECURITY;
+    assert_eq!(check_security(2048, max_security).unwrap(), 2048);
+    assert!(check_security(2049, max_security).is_err());
+
+    assert_eq!(check_security(4096, 4096).unwrap(), 4096);
+    assert!(check_security(4097, 4096).is_err());
+
+    // values past `u16::MAX` are refused rather than truncated, whatever the cap
+    assert!(check_security(65536 + 512, u16::MAX).is_err());
+    assert!(check_security(u64::MAX, u16::MAX).is_err());
+}
+
+#[test]
+fn test_lower_security_ceiling() {
+    // a deployment registering natives with a cap below the default
+    struct LowerVdfGasParameters;
+    impl VdfGasParameters for LowerVdfGasParameters {
+        const MAX_SECURITY: u16 = 1024;
+    }
+    let max_security = LowerVdfGasParameters::MAX_SECURITY;
+
+    let challenge = b"lower ceiling";
+    let difficulty = 100;
+    let security = check_security(512, max_security).unwrap();
+    let proof = vdf::WesolowskiVDFParams(security).new().solve(challenge, difficulty).unwrap();
+    assert_eq!(
+        verify(VDF_SCHEME_WESOLOWSKI, challenge, &proof, difficulty, security),
+        Some(true)
+    );
+
+    assert!(check_security(1024, max_security).is_ok());
+    // the default ceiling is now above the cap
+    assert!(check_security(2048, max_security).is_err());
+}
+
+#[test]
+fn test_verify_cost_grows_with_difficulty() {
+    use move_vm_types::gas_schedule::INITIAL_GAS_SCHEDULE;
+
+    let cost = |scheme, difficulty| {
+        verify_cost(&INITIAL_GAS_SCHEDULE, scheme, difficulty).unwrap().get()
+    };
+    let unit = native_gas(&INITIAL_GAS_SCHEDULE, NativeCostIndex::VDF_VERIFY, 1).unwrap().get();
+
+    // the mainnet difficulty costs one unit
+    assert_eq!(cost(VDF_SCHEME_PIETRZAK, VDF_BASELINE_DIFFICULTY), unit);
+    // doubling a realistic difficulty adds a halving round to a Pietrzak proof
+    assert!(cost(VDF_SCHEME_PIETRZAK, 200_000_000) > cost(VDF_SCHEME_PIETRZAK, 100_000_000));
+    assert_eq!(cost(VDF_SCHEME_PIETRZAK, 200_000_000), unit * 28 / 27);
+    assert!(cost(VDF_SCHEME_PIETRZAK, 100) < unit);
+    assert!(cost(VDF_SCHEME_PIETRZAK, 0) > 0);
+    assert_eq!(cost(VDF_SCHEME_PIETRZAK, u64::MAX), unit * 64 / 27);
+    // Wesolowski verification does not depend on the difficulty
+    assert_eq!(cost(VDF_SCHEME_WESOLOWSKI, 100_000_000), unit);
+    assert_eq!(cost(VDF_SCHEME_WESOLOWSKI, 200_000_000), unit);
+}
+
+#[test]
+fn test_extract_address_from_challenge() {
+    let mut challenge = vec![0xaa; 16];
+    challenge.extend_from_slice(&[0xbb; 16]);
+    challenge.extend_from_slice(b"rest of the challenge");
+
+    let (address, auth_key_prefix) = extract_address_from_challenge(&challenge).unwrap();
+    assert_eq!(address, AccountAddress::new([0xbb; AccountAddress::LENGTH]));
+    assert_eq!(auth_key_prefix, vec![0xaa; 16]);
+    // exactly an authentication key is enough
+    assert!(extract_address_from_challenge(&challenge[..32]).is_some());
+
+    // short challenges are rejected instead of panicking
+    assert_eq!(extract_address_from_challenge(&challenge[..10]), None);
+    assert_eq!(extract_address_from_challenge(&challenge[..31]), None);
+    assert_eq!(extract_address_from_challenge(&[]), None);
+}
@@ -8,10 +8,32 @@ use move_vm_types::{
     loaded_data::runtime_types::Type, natives::function::NativeResult, pop_arg, values::Value,
 };
 use smallvec::smallvec;
-use std::collections::VecDeque;
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+};
 
 use move_core_types::account_address::AccountAddress;
 
+thread_local! {
+    // Deterministic clock for unit tests. It starts at zero on every test thread and only
+    // moves when a test sets it, so reads never depend on wall-clock time.
+    static TEST_NOW_MICROS: Cell<u64> = Cell::new(0);
+
+    // Message of the last failed `assert_with_msg` on this thread, picked up by the unit
+    // test runner when it reports the abort.
+    static ASSERTION_MESSAGE: RefCell<Option<String>> = RefCell::new(None);
+}
+
+/// Abort code of a failed `UnitTest::assert_with_msg`.
+pub const EASSERTION_FAILED: u64 = 1;
+
+/// Returns and clears the message of the last failed `UnitTest::assert_with_msg` on the
+/// current thread.
+pub fn take_assertion_message() -> Option<String> {
+    ASSERTION_MESSAGE.with(|message| message.borrow_mut().take())
+}
+
 pub fn native_create_signers_for_testing(
     _context: &mut NativeContext,
     ty_args: Vec<Type>,
@@ -27,3 +49,78 @@ pub fn native_create_signers_for_testing(
 
     Ok(NativeResult::ok(ONE_GAS_UNIT, smallvec![signers]))
 }
+
+pub fn native_create_signers_for_testing_at(
+    _context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 1);
+
+    let addresses = pop_arg!(args, Vec<AccountAddress>);
+    let signers = Value::vector_for_testing_only(addresses.into_iter().map(Value::signer));
+
+    Ok(NativeResult::ok(ONE_GAS_UNIT, smallvec![signers]))
+}
+
+pub fn native_assert_with_msg(
+    _context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 2);
+
+    let msg = pop_arg!(args, Vec<u8>);
+    let cond = pop_arg!(args, bool);
+
+    if cond {
+        return Ok(NativeResult::ok(ONE_GAS_UNIT, smallvec![]));
+    }
+
+    let msg = String::from_utf8_lossy(&msg).into_owned();
+    ASSERTION_MESSAGE.with(|message| *message.borrow_mut() = Some(msg));
+
+    Ok(NativeResult::err(ONE_GAS_UNIT, EASSERTION_FAILED))
+}
+
+pub fn native_gas_used(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.is_empty());
+
+    let gas_used = context.gas_used();
+
+    Ok(NativeResult::ok(ONE_GAS_UNIT, smallvec![Value::u64(gas_used)]))
+}
+
+pub fn native_test_now_micros(
+    _context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.is_empty());
+
+    let now = TEST_NOW_MICROS.with(|now| now.get());
+
+    Ok(NativeResult::ok(ONE_GAS_UNIT, smallvec![Value::u64(now)]))
+}
+
+pub fn native_set_test_now_micros(
+    _context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 1);
+
+    let micros = pop_arg!(args, u64);
+    TEST_NOW_MICROS.with(|now| now.set(micros));
+
+    Ok(NativeResult::ok(ONE_GAS_UNIT, smallvec![]))
+}
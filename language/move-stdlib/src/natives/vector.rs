@@ -9,8 +9,9 @@ use move_vm_types::{
     loaded_data::runtime_types::Type,
     natives::function::{native_gas, NativeResult},
     pop_arg,
-    values::{Value, Vector, VectorRef},
+    values::{Reference, Value, Vector, VectorRef, POP_EMPTY_VEC},
 };
+use smallvec::smallvec;
 
 use std::collections::VecDeque;
 
@@ -22,7 +23,7 @@ pub fn native_empty(
     debug_assert!(ty_args.len() == 1);
     debug_assert!(args.is_empty());
 
-    let cost = native_gas(context.cost_table(), NativeCostIndex::EMPTY, 1);
+    let cost = native_gas(context.cost_table(), NativeCostIndex::EMPTY, 1)?;
     NativeResult::map_partial_vm_result_one(cost, Vector::empty(&ty_args[0]))
 }
 
@@ -35,7 +36,7 @@ pub fn native_length(
     debug_assert!(args.len() == 1);
 
     let r = pop_arg!(args, VectorRef);
-    let cost = native_gas(context.cost_table(), NativeCostIndex::LENGTH, 1);
+    let cost = native_gas(context.cost_table(), NativeCostIndex::LENGTH, 1)?;
     NativeResult::map_partial_vm_result_one(cost, r.len(&ty_args[0]))
 }
 
@@ -53,7 +54,7 @@ pub fn native_push_back(
         context.cost_table(),
         NativeCostIndex::PUSH_BACK,
         e.size().get() as usize,
-    );
+    )?;
     NativeResult::map_partial_vm_result_empty(cost, r.push_back(e, &ty_args[0]))
 }
 
@@ -67,7 +68,7 @@ pub fn native_borrow(
 
     let idx = pop_arg!(args, u64) as usize;
     let r = pop_arg!(args, VectorRef);
-    let cost = native_gas(context.cost_table(), NativeCostIndex::BORROW, 1);
+    let cost = native_gas(context.cost_table(), NativeCostIndex::BORROW, 1)?;
     NativeResult::map_partial_vm_result_one(
         cost,
         r.borrow_elem(idx, &ty_args[0])
@@ -84,7 +85,7 @@ pub fn native_pop(
     debug_assert!(args.len() == 1);
 
     let r = pop_arg!(args, VectorRef);
-    let cost = native_gas(context.cost_table(), NativeCostIndex::POP_BACK, 1);
+    let cost = native_gas(context.cost_table(), NativeCostIndex::POP_BACK, 1)?;
     NativeResult::map_partial_vm_result_one(cost, r.pop(&ty_args[0]).map_err(native_error_to_abort))
 }
 
@@ -97,7 +98,7 @@ pub fn native_destroy_empty(
     debug_assert!(args.len() == 1);
 
     let v = pop_arg!(args, Vector);
-    let cost = native_gas(context.cost_table(), NativeCostIndex::DESTROY_EMPTY, 1);
+    let cost = native_gas(context.cost_table(), NativeCostIndex::DESTROY_EMPTY, 1)?;
     NativeResult::map_partial_vm_result_empty(
         cost,
         v.destroy_empty(&ty_args[0]).map_err(native_error_to_abort),
@@ -115,7 +116,7 @@ pub fn native_swap(
     let idx2 = pop_arg!(args, u64) as usize;
     let idx1 = pop_arg!(args, u64) as usize;
     let r = pop_arg!(args, VectorRef);
-    let cost = native_gas(context.cost_table(), NativeCostIndex::SWAP, 1);
+    let cost = native_gas(context.cost_table(), NativeCostIndex::SWAP, 1)?;
     NativeResult::map_partial_vm_result_empty(
         cost,
         r.swap(idx1, idx2, &ty_args[0])
@@ -123,6 +124,67 @@ pub fn native_swap(
     )
 }
 
+/// Rust implementation of Move's `native public fun argmax_u128(v: &vector<u128>): (u128, u64)`
+/// Returns the largest element and the index of its first occurrence. Aborts like `pop_back`
+/// if `v` is empty.
+pub fn native_argmax_u128(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    arg_extreme_u128(context, args, |elem, max| elem > max)
+}
+
+/// Rust implementation of Move's `native public fun argmin_u128(v: &vector<u128>): (u128, u64)`
+/// Returns the smallest element and the index of its first occurrence. Aborts like `pop_back`
+/// if `v` is empty.
+pub fn native_argmin_u128(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    arg_extreme_u128(context, args, |elem, min| elem < min)
+}
+
+// Scans the vector for the element that `replaces` every other one. `replaces` is strict, so
+// the first index wins ties.
+fn arg_extreme_u128(
+    context: &mut NativeContext,
+    mut args: VecDeque<Value>,
+    replaces: fn(u128, u128) -> bool,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(args.len() == 1);
+
+    let r = pop_arg!(args, VectorRef);
+    let len = r.len(&Type::U128)?.value_as::<u64>()? as usize;
+    // one borrow per element
+    let cost = native_gas(context.cost_table(), NativeCostIndex::BORROW, len)?;
+
+    if len == 0 {
+        return Ok(NativeResult::err(cost, POP_EMPTY_VEC));
+    }
+
+    let (mut best_elem, mut best_idx) = (0u128, 0usize);
+    for idx in 0..len {
+        let elem = r
+            .borrow_elem(idx, &Type::U128)?
+            .value_as::<Reference>()?
+            .read_ref()?
+            .value_as::<u128>()?;
+        if idx == 0 || replaces(elem, best_elem) {
+            best_elem = elem;
+            best_idx = idx;
+        }
+    }
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::u128(best_elem), Value::u64(best_idx as u64)],
+    ))
+}
+
 fn native_error_to_abort(err: PartialVMError) -> PartialVMError {
     let (major_status, sub_status_opt, message_opt, indices, offsets) = err.all_data();
     let new_err = match major_status {
@@ -11,6 +11,11 @@ module Std::BCSTests {
     struct Box127<T> has copy, drop, store { x: Box63<Box63<T>> }
     struct Box255<T> has copy, drop, store { x: Box127<Box127<T>> }
 
+    struct Point has drop { x: u64, y: u64 }
+    struct SamePoint has drop { x: u64, y: u64 }
+    struct RenamedPoint has drop { x: u64, z: u64 }
+    struct WiderPoint has drop { x: u64, y: u128 }
+
     #[test]
     fun bcs_address() {
         let addr = @0x89b9f9d1fadc027cf9532d6f99041522;
@@ -92,4 +97,39 @@ module Std::BCSTests {
     fun encode_257() {
         BCS::to_bytes(&Box { x: box255(true) });
     }
+
+    #[test]
+    fun type_layout_hash_same_fields() {
+        assert!(BCS::type_layout_hash<Point>() == BCS::type_layout_hash<SamePoint>(), 0);
+        assert!(BCS::type_layout_hash<Box<Point>>() == BCS::type_layout_hash<Box<SamePoint>>(), 1);
+        assert!(BCS::type_layout_hash<u64>() == BCS::type_layout_hash<u64>(), 2);
+    }
+
+    #[test]
+    fun type_layout_hash_changed_field() {
+        let point = BCS::type_layout_hash<Point>();
+        assert!(copy point != BCS::type_layout_hash<RenamedPoint>(), 0);
+        assert!(point != BCS::type_layout_hash<WiderPoint>(), 1);
+        assert!(BCS::type_layout_hash<Box<u64>>() != BCS::type_layout_hash<Box<u128>>(), 2);
+        assert!(BCS::type_layout_hash<vector<u8>>() != BCS::type_layout_hash<u8>(), 3);
+    }
+
+    #[test]
+    fun is_canonical_round_trip() {
+        assert!(BCS::is_canonical<u64>(BCS::to_bytes(&1)), 0);
+        assert!(BCS::is_canonical<bool>(x"01"), 1);
+        assert!(BCS::is_canonical<vector<u8>>(x"020f0f"), 2);
+        assert!(BCS::is_canonical<Box<u128>>(BCS::to_bytes(&Box { x: 7u128 })), 3);
+    }
+
+    #[test]
+    fun is_canonical_rejects_non_canonical() {
+        // bools are 0 or 1
+        assert!(!BCS::is_canonical<bool>(x"02"), 0);
+        // trailing and missing bytes
+        assert!(!BCS::is_canonical<u8>(x"0100"), 1);
+        assert!(!BCS::is_canonical<u64>(x"01"), 2);
+        // length 1 with a padded ULEB128 prefix
+        assert!(!BCS::is_canonical<vector<u8>>(x"81000f"), 3);
+    }
 }
@@ -0,0 +1,49 @@
+#[test_only]
+module Std::UnitTestTests {
+    use Std::Signer;
+    use Std::UnitTest;
+    use Std::Vector;
+
+    #[test]
+    fun test_clock_advances() {
+        UnitTest::set_now_micros(1000);
+        assert!(UnitTest::now_micros() == 1000, 0);
+
+        UnitTest::set_now_micros(UnitTest::now_micros() + 500);
+        assert!(UnitTest::now_micros() == 1500, 1);
+    }
+
+    #[test]
+    fun test_signers_at_chosen_addresses() {
+        let addresses = vector[@0x1, @0xCAFE, @0x42];
+        let signers = UnitTest::create_signers_for_testing_at(copy addresses);
+        assert!(Vector::length(&signers) == 3, 0);
+
+        let i = 0;
+        while (i < 3) {
+            let s = Vector::borrow(&signers, i);
+            assert!(*Signer::borrow_address(s) == *Vector::borrow(&addresses, i), 1);
+            i = i + 1;
+        };
+    }
+
+    #[test]
+    fun test_assert_with_msg_holds() {
+        UnitTest::assert_with_msg(true, b"unreachable");
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 1)]
+    fun test_assert_with_msg_fails() {
+        UnitTest::assert_with_msg(false, b"expected failure");
+    }
+
+    #[test]
+    fun test_gas_used_increases() {
+        let before = UnitTest::gas_used();
+        let i = 0;
+        while (i < 10) i = i + 1;
+        let after = UnitTest::gas_used();
+        assert!(after > before, 0);
+    }
+}
@@ -500,4 +500,52 @@ module Std::VectorTests {
             NotDroppable {}
         );
     }
+
+    #[test]
+    fun argmax_u128_unique_max() {
+        let (max, idx) = V::argmax_u128(&vector[3, 9, 1, 7]);
+        assert!(max == 9, 0);
+        assert!(idx == 1, 1);
+
+        let (max, idx) = V::argmax_u128(&V::singleton(5));
+        assert!(max == 5, 2);
+        assert!(idx == 0, 3);
+    }
+
+    #[test]
+    fun argmax_u128_tie_picks_first() {
+        let (max, idx) = V::argmax_u128(&vector[2, 8, 0, 8, 8]);
+        assert!(max == 8, 0);
+        assert!(idx == 1, 1);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 2)]
+    fun argmax_u128_empty() {
+        V::argmax_u128(&V::empty<u128>());
+    }
+
+    #[test]
+    fun argmin_u128_unique_min() {
+        let (min, idx) = V::argmin_u128(&vector[3, 9, 1, 7]);
+        assert!(min == 1, 0);
+        assert!(idx == 2, 1);
+
+        let (min, idx) = V::argmin_u128(&V::singleton(5));
+        assert!(min == 5, 2);
+        assert!(idx == 0, 3);
+    }
+
+    #[test]
+    fun argmin_u128_tie_picks_first() {
+        let (min, idx) = V::argmin_u128(&vector[4, 0, 8, 0, 0]);
+        assert!(min == 0, 0);
+        assert!(idx == 1, 1);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 2)]
+    fun argmin_u128_empty() {
+        V::argmin_u128(&V::empty<u128>());
+    }
 }
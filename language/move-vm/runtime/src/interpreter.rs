@@ -496,6 +496,11 @@ impl Interpreter {
         Ok(())
     }
 
+    /// Return the number of active Move frames, including the one currently executing.
+    pub(crate) fn call_depth(&self) -> usize {
+        self.call_stack.0.len() + 1
+    }
+
     /// Generate a string which is the status of the interpreter: call stack, current bytecode
     /// stream, locals and operand stack.
     ///
@@ -22,7 +22,7 @@ use move_bytecode_verifier::{self, cyclic_dependencies, dependencies, script_sig
 use move_core_types::{
     identifier::{IdentStr, Identifier},
     language_storage::{ModuleId, StructTag, TypeTag},
-    value::{MoveStructLayout, MoveTypeLayout},
+    value::{MoveFieldLayout, MoveStructLayout, MoveTypeLayout},
     vm_status::StatusCode,
 };
 use move_vm_types::{
@@ -1300,6 +1300,13 @@ impl<'a> Resolver<'a> {
         self.loader.type_to_type_layout(ty)
     }
 
+    pub(crate) fn type_to_fully_annotated_layout(
+        &self,
+        ty: &Type,
+    ) -> PartialVMResult<MoveTypeLayout> {
+        self.loader.type_to_fully_annotated_layout(ty)
+    }
+
     //
     // Type resolution
     //
@@ -2137,12 +2144,80 @@ impl Loader {
         })
     }
 
+    fn struct_gidx_to_fully_annotated_layout(
+        &self,
+        gidx: usize,
+        ty_args: &[Type],
+        depth: usize,
+    ) -> PartialVMResult<MoveStructLayout> {
+        let struct_type = self.module_cache.read().struct_at(gidx);
+        let module = match self.module_cache.read().module_at(&struct_type.module) {
+            Some(module) => module,
+            None => {
+                return Err(
+                    PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                        .with_message(format!("module {} is not loaded", struct_type.module)),
+                )
+            }
+        };
+        let compiled = module.module();
+        let field_defs = match &compiled.struct_def_at(struct_type.struct_def).field_information {
+            StructFieldInformation::Declared(field_defs) => field_defs,
+            StructFieldInformation::Native => {
+                return Err(
+                    PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                        .with_message(format!("no fields for native struct {}", struct_type.name)),
+                )
+            }
+        };
+        let field_layouts = field_defs
+            .iter()
+            .zip(&struct_type.fields)
+            .map(|(field_def, ty)| {
+                let ty = ty.subst(ty_args)?;
+                let layout = self.type_to_fully_annotated_layout_impl(&ty, depth + 1)?;
+                let name = compiled.identifier_at(field_def.name).to_owned();
+                Ok(MoveFieldLayout::new(name, layout))
+            })
+            .collect::<PartialVMResult<Vec<_>>>()?;
+        Ok(MoveStructLayout::with_fields(field_layouts))
+    }
+
+    // Like `type_to_type_layout_impl`, but struct layouts carry their field names.
+    fn type_to_fully_annotated_layout_impl(
+        &self,
+        ty: &Type,
+        depth: usize,
+    ) -> PartialVMResult<MoveTypeLayout> {
+        if depth > VALUE_DEPTH_MAX {
+            return Err(PartialVMError::new(StatusCode::VM_MAX_VALUE_DEPTH_REACHED));
+        }
+        Ok(match ty {
+            Type::Vector(ty) => MoveTypeLayout::Vector(Box::new(
+                self.type_to_fully_annotated_layout_impl(ty, depth + 1)?,
+            )),
+            Type::Struct(gidx) => MoveTypeLayout::Struct(
+                self.struct_gidx_to_fully_annotated_layout(*gidx, &[], depth)?,
+            ),
+            Type::StructInstantiation(gidx, ty_args) => MoveTypeLayout::Struct(
+                self.struct_gidx_to_fully_annotated_layout(*gidx, ty_args, depth)?,
+            ),
+            _ => self.type_to_type_layout_impl(ty, depth)?,
+        })
+    }
+
     pub(crate) fn type_to_type_tag(&self, ty: &Type) -> PartialVMResult<TypeTag> {
         self.type_to_type_tag_impl(ty)
     }
     pub(crate) fn type_to_type_layout(&self, ty: &Type) -> PartialVMResult<MoveTypeLayout> {
         self.type_to_type_layout_impl(ty, 1)
     }
+    pub(crate) fn type_to_fully_annotated_layout(
+        &self,
+        ty: &Type,
+    ) -> PartialVMResult<MoveTypeLayout> {
+        self.type_to_fully_annotated_layout_impl(ty, 1)
+    }
 }
 
 // Public APIs for external uses.
@@ -5,7 +5,7 @@ use crate::{interpreter::Interpreter, loader::Resolver};
 use move_binary_format::errors::{PartialVMError, PartialVMResult};
 use move_core_types::{
     account_address::AccountAddress,
-    gas_schedule::CostTable,
+    gas_schedule::{CostTable, GasAlgebra},
     identifier::Identifier,
     value::MoveTypeLayout,
     vm_status::{StatusCode, StatusType},
@@ -86,6 +86,16 @@ impl<'a> NativeContext<'a> {
             .debug_print_stack_trace(buf, self.resolver.loader())
     }
 
+    /// Return the depth of the Move call stack the native was called from.
+    pub fn call_depth(&self) -> usize {
+        self.interpreter.call_depth()
+    }
+
+    /// Return the gas consumed so far by the current execution.
+    pub fn gas_used(&self) -> u64 {
+        self.gas_status.gas_used().get()
+    }
+
     pub fn cost_table(&self) -> &CostTable {
         self.gas_status.cost_table()
     }
@@ -111,4 +121,16 @@ impl<'a> NativeContext<'a> {
             Err(_) => Ok(None),
         }
     }
+
+    /// Like `type_to_type_layout`, but struct layouts carry their field names.
+    pub fn type_to_fully_annotated_layout(
+        &self,
+        ty: &Type,
+    ) -> PartialVMResult<Option<MoveTypeLayout>> {
+        match self.resolver.type_to_fully_annotated_layout(ty) {
+            Ok(ty_layout) => Ok(Some(ty_layout)),
+            Err(e) if e.major_status().status_type() == StatusType::InvariantViolation => Err(e),
+            Err(_) => Ok(None),
+        }
+    }
 }
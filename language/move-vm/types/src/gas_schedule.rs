@@ -35,6 +35,7 @@ static ZERO_COST_SCHEDULE: Lazy<CostTable> = Lazy::new(zero_cost_schedule);
 /// Every client must use an instance of this type to interact with the Move VM.
 pub struct GasStatus<'a> {
     cost_table: &'a CostTable,
+    initial_gas: InternalGasUnits<GasCarrier>,
     gas_left: InternalGasUnits<GasCarrier>,
     charge: bool,
 }
@@ -45,8 +46,10 @@ impl<'a> GasStatus<'a> {
     /// Charge for every operation and fail when there is no more gas to pay for operations.
     /// This is the instantiation that must be used when executing a user script.
     pub fn new(cost_table: &'a CostTable, gas_left: GasUnits<GasCarrier>) -> Self {
+        let gas_left = cost_table.gas_constants.to_internal_units(gas_left);
         Self {
-            gas_left: cost_table.gas_constants.to_internal_units(gas_left),
+            initial_gas: gas_left,
+            gas_left,
             cost_table,
             charge: true,
         }
@@ -58,6 +61,7 @@ impl<'a> GasStatus<'a> {
     /// code that does not have to charge the user.
     pub fn new_unmetered() -> Self {
         Self {
+            initial_gas: InternalGasUnits::new(0),
             gas_left: InternalGasUnits::new(0),
             cost_table: &ZERO_COST_SCHEDULE,
             charge: false,
@@ -76,6 +80,16 @@ impl<'a> GasStatus<'a> {
             .to_external_units(self.gas_left)
     }
 
+    /// Return the gas consumed since this `GasStatus` was created.
+    ///
+    /// The value never decreases while the status is in use, so two readings can be
+    /// subtracted to measure the cost of the code executed between them.
+    pub fn gas_used(&self) -> GasUnits<GasCarrier> {
+        self.cost_table
+            .gas_constants
+            .to_external_units(self.initial_gas.sub(self.gas_left))
+    }
+
     /// Charge a given amount of gas and fail if not enough gas units are left.
     pub fn deduct_gas(&mut self, amount: InternalGasUnits<GasCarrier>) -> PartialVMResult<()> {
         if !self.charge {
@@ -478,6 +492,16 @@ pub enum NativeCostIndex {
     DESTROY_SIGNER = 16,
     EMIT_EVENT = 17,
     //////// 0L ////////
+    // The native table stored on chain has exactly `NUMBER_OF_NATIVE_FUNCTIONS` entries, so
+    // 0L natives added since share the slot of the closest existing native instead of
+    // growing it:
+    // - DECIMAL: the u128 helpers of XMath and XBits, XHash's order preserving encodings
+    //   and XEvm's semver_compare and log_matches_filter, charged per word or byte handled.
+    // - KECCAK_256: every other hash in XHash (keccak 512, sha3 512, blake3 and the hashes
+    //   built from keccak), XMerkle, XAddress and XEvm, charged per byte hashed.
+    // - ETH_SIGNATURE_VERIFY: Ed25519::ed25519ph_verify and P256::webauthn_verify, each
+    //   about as costly as one secp256k1 verification.
+    // A native that cannot be priced this way needs a new slot and a gas schedule upgrade.
     VDF_VERIFY = 18,
     VDF_PARSE = 19,
     DECIMAL = 20,
@@ -106,15 +106,19 @@ impl NativeResult {
 
 /// Return the native gas entry in `CostTable` for the given key.
 /// The key is the specific native function index known to `CostTable`.
+/// Fails with an invariant violation if the table has no entry for the key.
 pub fn native_gas(
     table: &CostTable,
     key: NativeCostIndex,
     size: usize,
-) -> InternalGasUnits<GasCarrier> {
-    let gas_amt = table.native_cost(key as u8);
+) -> PartialVMResult<InternalGasUnits<GasCarrier>> {
+    let gas_amt = table.native_cost(key as u8).ok_or_else(|| {
+        PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+            .with_message(format!("no gas cost for native {:?}", key))
+    })?;
     let memory_size = AbstractMemorySize::new(std::cmp::max(1, size) as GasCarrier);
     debug_assert!(memory_size.get() > 0);
-    gas_amt.total().mul(memory_size)
+    Ok(gas_amt.total().mul(memory_size))
 }
 
 /// Return the argument at the top of the stack.
@@ -0,0 +1,19 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    gas_schedule::{NativeCostIndex, INITIAL_GAS_SCHEDULE},
+    natives::function::native_gas,
+};
+use move_core_types::vm_status::StatusCode;
+
+#[test]
+fn gas_from_a_short_cost_table() {
+    let mut table = INITIAL_GAS_SCHEDULE.clone();
+    assert!(native_gas(&table, NativeCostIndex::DECIMAL, 1).is_ok());
+    // a stored schedule without the entry fails the native instead of panicking
+    table.native_table.truncate(NativeCostIndex::DECIMAL as usize);
+    let err = native_gas(&table, NativeCostIndex::DECIMAL, 1).unwrap_err();
+    assert_eq!(err.major_status(), StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR);
+    assert!(native_gas(&table, NativeCostIndex::SHA2_256, 1).is_ok());
+}
@@ -2,3 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod function;
+
+#[cfg(test)]
+mod function_tests;
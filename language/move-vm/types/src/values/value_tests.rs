@@ -120,3 +120,28 @@ fn global_value_non_struct() -> PartialVMResult<()> {
 
     Ok(())
 }
+
+#[test]
+fn vector_casts() -> PartialVMResult<()> {
+    let bools: Vec<bool> = Value::vector_bool(vec![true, false]).value_as()?;
+    assert_eq!(bools, vec![true, false]);
+
+    let ints: Vec<u128> = Value::vector_u128(vec![1, u128::MAX]).value_as()?;
+    assert_eq!(ints, vec![1, u128::MAX]);
+
+    let nested = Value::vector_for_testing_only(vec![
+        Value::vector_u8(vec![1, 2]),
+        Value::vector_u8(vec![]),
+    ]);
+    let inner: Vec<Vec<u8>> = nested
+        .value_as::<Vec<Value>>()?
+        .into_iter()
+        .map(|v| v.value_as::<Vec<u8>>())
+        .collect::<PartialVMResult<_>>()?;
+    assert_eq!(inner, vec![vec![1, 2], vec![]]);
+
+    assert!(Value::vector_u8(vec![1]).value_as::<Vec<Value>>().is_err());
+    assert!(Value::vector_u64(vec![1]).value_as::<Vec<u128>>().is_err());
+
+    Ok(())
+}
@@ -1136,6 +1136,48 @@ impl VMValueCast<Vec<u8>> for Value {
     }
 }
 
+impl VMValueCast<Vec<Value>> for Value {
+    fn cast(self) -> PartialVMResult<Vec<Value>> {
+        match self.0 {
+            ValueImpl::Container(Container::Vec(r)) => {
+                Ok(take_unique_ownership(r)?.into_iter().map(Value).collect())
+            }
+            v => Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR)
+                .with_message(format!("cannot cast {:?} to vector of vectors or structs", v,))),
+        }
+    }
+}
+
+impl VMValueCast<Vec<u128>> for Value {
+    fn cast(self) -> PartialVMResult<Vec<u128>> {
+        match self.0 {
+            ValueImpl::Container(Container::VecU128(r)) => take_unique_ownership(r),
+            v => Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR)
+                .with_message(format!("cannot cast {:?} to vector<u128>", v,))),
+        }
+    }
+}
+
+impl VMValueCast<Vec<bool>> for Value {
+    fn cast(self) -> PartialVMResult<Vec<bool>> {
+        match self.0 {
+            ValueImpl::Container(Container::VecBool(r)) => take_unique_ownership(r),
+            v => Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR)
+                .with_message(format!("cannot cast {:?} to vector<bool>", v,))),
+        }
+    }
+}
+
+impl VMValueCast<Vec<AccountAddress>> for Value {
+    fn cast(self) -> PartialVMResult<Vec<AccountAddress>> {
+        match self.0 {
+            ValueImpl::Container(Container::VecAddress(r)) => take_unique_ownership(r),
+            v => Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR)
+                .with_message(format!("cannot cast {:?} to vector<address>", v,))),
+        }
+    }
+}
+
 impl VMValueCast<SignerRef> for Value {
     fn cast(self) -> PartialVMResult<SignerRef> {
         match self.0 {
@@ -93,6 +93,16 @@ impl FailureReason {
         FailureReason::Aborted("Test was not expected to abort".to_string(), abort_code)
     }
 
+    pub fn assertion_failed(abort_code: u64, message: String) -> Self {
+        FailureReason::Aborted(
+            format!(
+                "Assertion failed: \"{}\". Test was not expected to abort",
+                message
+            ),
+            abort_code,
+        )
+    }
+
     pub fn timeout() -> Self {
         FailureReason::Timeout("Test timed out".to_string())
     }
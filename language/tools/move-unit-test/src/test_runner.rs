@@ -31,6 +31,7 @@ use move_stackless_bytecode_interpreter::{
     shared::bridge::{adapt_move_vm_change_set, adapt_move_vm_result},
     StacklessBytecodeInterpreter,
 };
+use move_stdlib::natives::unit_test::take_assertion_message;
 use move_vm_runtime::{move_vm::MoveVM, native_functions::NativeFunctionTable};
 use move_vm_test_utils::InMemoryStorage;
 use move_vm_types::gas_schedule::{zero_cost_schedule, GasStatus};
@@ -314,6 +315,8 @@ impl SharedTestingConfig {
         };
 
         for (function_name, test_info) in &test_plan.tests {
+            // Drop any assertion message left over from a previous test on this thread.
+            take_assertion_message();
             let (cs_result, exec_result, test_run_info) =
                 self.execute_via_move_vm(test_plan, function_name, test_info);
             if self.check_stackless_vm {
@@ -389,9 +392,13 @@ impl SharedTestingConfig {
                     // Expected the test to not abort, but it aborted with `code`
                     (None, Some(code)) => {
                         fail(function_name);
+                        let failure_reason = match take_assertion_message() {
+                            Some(message) => FailureReason::assertion_failed(code, message),
+                            None => FailureReason::aborted(code),
+                        };
                         stats.test_failure(
                             TestFailure::new(
-                                FailureReason::aborted(code),
+                                failure_reason,
                                 test_run_info,
                                 Some(err),
                                 save_session_state(),
@@ -0,0 +1,19 @@
+module 0x1::A {
+    use Std::UnitTest;
+
+    #[test]
+    fun assert_with_msg_holds() {
+        UnitTest::assert_with_msg(true, b"never shown");
+    }
+
+    #[test]
+    fun assert_with_msg_fails() {
+        UnitTest::assert_with_msg(1 + 1 == 3, b"arithmetic is broken");
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 1)]
+    fun assert_with_msg_expected_failure() {
+        UnitTest::assert_with_msg(false, b"expected");
+    }
+}